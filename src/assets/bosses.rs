@@ -0,0 +1,193 @@
+use super::*;
+
+use super::{
+    config::{BodyConfig, EnemyConfig, ExplosionConfig, ShotConfig},
+    waves::WavesConfig,
+};
+
+use crate::model::{
+    ActorKind, AttackTrigger, BossAttack, BossMotion, Coord, GasolineSpill, Hp, ScreenShake, Time,
+    VulnerabilityStats,
+};
+
+use std::collections::BTreeMap;
+
+/// A boss made of several parts, each being a separate actor.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BossConfig {
+    /// Blocks in that radius around the boss are removed when it spawns.
+    pub clear_radius: Coord,
+    /// Pushes everything away from the boss when it spawns.
+    pub spawn_explosion: Option<ExplosionConfig>,
+    /// Shot patterns, referenced by name in the attacks of the parts.
+    /// Ordered, so that the config hash is reproducible.
+    #[serde(default)]
+    pub shots: BTreeMap<String, ShotConfig>,
+    pub parts: Vec<BossPartConfig>,
+    /// Entered in order, once the trigger of the next phase fires.
+    /// Before the first phase, the parts behave as configured in [BossConfig::parts].
+    #[serde(default)]
+    pub phases: Vec<BossPhaseConfig>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BossPartConfig {
+    /// Used by the phases to refer to the part.
+    #[serde(default)]
+    pub name: String,
+    /// Offset from the position of the boss.
+    pub offset: vec2<Coord>,
+    pub body: BodyConfig,
+    pub hp: Hp,
+    pub contact_damage: Hp,
+    #[serde(default)]
+    pub vulnerability: VulnerabilityStats,
+    /// The sprite of the part.
+    pub kind: ActorKind,
+    #[serde(default)]
+    pub motion: BossMotion,
+    #[serde(default)]
+    pub attacks: Vec<BossAttackConfig>,
+    /// Killing the core kills the whole boss.
+    #[serde(default)]
+    pub core: bool,
+    #[serde(default)]
+    pub stops_barrel: bool,
+    /// Gasoline spilled when the part dies.
+    #[serde(default)]
+    pub death_spill: Option<GasolineSpill>,
+    #[serde(default)]
+    pub death_explosion: Option<ExplosionConfig>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BossAttackConfig {
+    /// Name of the shot in [BossConfig::shots].
+    pub shot: String,
+    pub trigger: AttackTrigger,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BossPhaseConfig {
+    #[serde(default)]
+    pub name: String,
+    pub trigger: PhaseTrigger,
+    /// Changes to the parts, by their name.
+    #[serde(default)]
+    pub parts: BTreeMap<String, BossPartPhaseConfig>,
+    #[serde(default)]
+    pub minions: Option<MinionsConfig>,
+    /// Shake the screen when the phase starts.
+    #[serde(default)]
+    pub screen_shake: Option<ScreenShake>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum PhaseTrigger {
+    /// When the total health of the boss drops to that fraction of the max.
+    Health { ratio: R32 },
+    /// After spending that much time in the previous phase.
+    Timer { time: Time },
+}
+
+/// Overrides for a part during a phase. Missing fields keep the previous value.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct BossPartPhaseConfig {
+    pub motion: Option<BossMotion>,
+    pub attacks: Option<Vec<BossAttackConfig>>,
+    pub vulnerability: Option<VulnerabilityStats>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MinionsConfig {
+    /// Names of the enemies to spawn, picked at random.
+    pub enemies: Vec<String>,
+    /// Delay between the spawns.
+    pub delay: Time,
+    /// Distance from the boss at which the minions appear.
+    pub radius: Coord,
+    /// Do not spawn while there are that many enemies alive.
+    pub max_enemies: usize,
+}
+
+impl BossConfig {
+    /// Resolve the shot names of the attacks.
+    /// The names are checked when loading the bosses, so it only fails for unchecked configs.
+    pub fn attacks(&self, attacks: &[BossAttackConfig]) -> anyhow::Result<Vec<BossAttack>> {
+        attacks
+            .iter()
+            .map(|attack| {
+                let shot = self
+                    .shots
+                    .get(&attack.shot)
+                    .ok_or_else(|| anyhow::anyhow!("Shot {:?} not found", attack.shot))?;
+                Ok(BossAttack {
+                    shot: shot.clone(),
+                    trigger: attack.trigger.clone(),
+                    cooldown: Time::ZERO,
+                })
+            })
+            .collect()
+    }
+
+    /// Check that the shots, parts and minions referenced by name exist.
+    fn validate(&self, enemies: &HashMap<String, EnemyConfig>) -> anyhow::Result<()> {
+        let check_attacks = |attacks: &[BossAttackConfig]| self.attacks(attacks).map(|_| ());
+
+        for part in &self.parts {
+            check_attacks(&part.attacks).with_context(|| format!("in part {:?}", part.name))?;
+        }
+        for phase in &self.phases {
+            for (name, changes) in &phase.parts {
+                anyhow::ensure!(
+                    self.parts.iter().any(|part| part.name == *name),
+                    "Part {:?} of phase {:?} not found",
+                    name,
+                    phase.name
+                );
+                if let Some(attacks) = &changes.attacks {
+                    check_attacks(attacks).with_context(|| format!("in phase {:?}", phase.name))?;
+                }
+            }
+            if let Some(minions) = &phase.minions {
+                for enemy in &minions.enemies {
+                    anyhow::ensure!(
+                        enemies.contains_key(enemy),
+                        "Minion {:?} of phase {:?} not found",
+                        enemy,
+                        phase.name
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Load the bosses and check the names they reference,
+    /// including the boss of the waves.
+    pub async fn load_all(
+        path: impl AsRef<std::path::Path>,
+        enemies: &HashMap<String, EnemyConfig>,
+        waves: &WavesConfig,
+    ) -> anyhow::Result<HashMap<String, BossConfig>> {
+        let path = path.as_ref();
+        log::debug!("Loading folder {:?}", path);
+
+        let list: Vec<String> = crate::util::load_file(&path.join("_list.ron")).await?;
+
+        let mut bosses = HashMap::new();
+        for name in list {
+            let boss: BossConfig =
+                crate::util::load_file(&path.join(&name).with_extension("ron")).await?;
+            boss.validate(enemies)
+                .with_context(|| format!("when loading boss {:?}", name))?;
+            bosses.insert(name, boss);
+        }
+
+        if let Some(boss) = &waves.boss {
+            anyhow::ensure!(bosses.contains_key(boss), "Boss {:?} not found", boss);
+        }
+        Ok(bosses)
+    }
+}
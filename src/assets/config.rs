@@ -1,18 +1,33 @@
 use super::*;
 
 use crate::model::{
-    ActorAI, ActorKind, BlockKind, Coord, Hp, OnFire, ProjectileAI, ProjectileKind, Shape,
-    ShotPattern, Stats, Time, VulnerabilityStats,
+    ActorAI, ActorKind, BlockKind, Coord, DamageSource, GasolineSpill, Hp, Position, ProjectileAI,
+    ProjectileKind, Shape, ShotPattern, Stacking, Stats, StatusEffect, StatusKind, SteeringConfig,
+    Time, VulnerabilityStats,
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Config {
     /// Size of the world torus.
     pub world_size: vec2<Coord>,
+    /// Number of simulation ticks per second.
+    pub tick_rate: R32,
+    /// Size of the cells in the collision broadphase grid.
+    pub broadphase_cell_size: Coord,
     pub explosions_affect_projectiles: bool,
+    /// Whether explosions damage the fraction that caused them.
+    pub explosions_friendly_fire: bool,
     /// Whether bullets ignite gasoline when passing by.
     pub bullets_ignite_on_hover: bool,
     pub death_explosion: Option<ExplosionConfig>,
+    /// Fire spreading from the burning actors and blocks.
+    pub fire: FireConfig,
+    /// While two bodies stay in contact, contact and runover damage is applied again after that time.
+    /// If not set, the damage is applied once per contact.
+    pub contact_cooldown: Option<Time>,
+    pub pathfinding: PathfindingConfig,
+    /// How strongly the force fields of the level affect different things.
+    pub forces: ForceResponseConfig,
     pub death_drop_heal_chance: R32,
     pub pickups: PickupConfig,
     pub player: PlayerConfig,
@@ -23,6 +38,119 @@ pub struct Config {
 pub struct LevelConfig {
     pub foreground: ProcGenConfig,
     pub background: ProcGenConfig,
+    /// Wind, currents and vortexes.
+    #[serde(default)]
+    pub forces: Vec<ForceFieldConfig>,
+}
+
+impl LevelConfig {
+    /// Velocity of the flow at the position, summed over all the force fields.
+    pub fn flow(&self, position: Position, time: Time) -> vec2<Coord> {
+        self.forces
+            .iter()
+            .fold(vec2::ZERO, |acc, field| acc + field.flow(position, time))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum ForceFieldConfig {
+    /// Constant flow in one direction.
+    /// Limited to a zone it makes a current.
+    Wind {
+        velocity: vec2<Coord>,
+        #[serde(default)]
+        gust: Option<GustConfig>,
+        /// The whole world if not set.
+        #[serde(default)]
+        zone: Option<ForceZone>,
+    },
+    /// Swirl around the center, weakening towards the edge.
+    Vortex {
+        center: vec2<Coord>,
+        radius: Coord,
+        /// Speed of the flow around the center, positive is counterclockwise.
+        spin: Coord,
+        /// Speed of the flow towards the center, negative pushes away.
+        #[serde(default)]
+        pull: Coord,
+    },
+}
+
+/// The wind periodically getting stronger and weaker.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct GustConfig {
+    /// Fraction of the velocity the wind changes by.
+    pub strength: R32,
+    pub period: Time,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct ForceZone {
+    pub center: vec2<Coord>,
+    pub radius: Coord,
+}
+
+impl ForceFieldConfig {
+    /// Velocity of the flow at the position.
+    pub fn flow(&self, position: Position, time: Time) -> vec2<Coord> {
+        let world_size = position.world_size();
+        match self {
+            Self::Wind {
+                velocity,
+                gust,
+                zone,
+            } => {
+                let inside = zone.is_none_or(|zone| {
+                    let center = Position::from_world(zone.center, world_size);
+                    center.distance(position) <= zone.radius
+                });
+                if !inside {
+                    return vec2::ZERO;
+                }
+                let gust = gust.map_or(R32::ONE, |gust| {
+                    let phase = (time / gust.period).as_f32() * std::f32::consts::TAU;
+                    R32::ONE + gust.strength * r32(phase.sin())
+                });
+                *velocity * gust
+            }
+            Self::Vortex {
+                center,
+                radius,
+                spin,
+                pull,
+            } => {
+                let center = Position::from_world(*center, world_size);
+                let delta = position.delta_to(center);
+                let distance = delta.len();
+                if distance >= *radius || distance <= Coord::ZERO {
+                    return vec2::ZERO;
+                }
+                let falloff = R32::ONE - distance / *radius;
+                let dir = delta / distance;
+                (dir * *pull - dir.rotate_90() * *spin) * falloff
+            }
+        }
+    }
+}
+
+/// Multipliers of the force fields flow for different things.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ForceResponseConfig {
+    pub particles: R32,
+    pub projectiles: R32,
+    pub actors: R32,
+    pub gasoline: R32,
+    /// How much faster the fire spreads downwind (and slower upwind)
+    /// per unit of the flow speed.
+    pub fire: R32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PathfindingConfig {
+    /// Size of the cells in the enemy navigation grid.
+    pub cell_size: Coord,
+    /// Extra distance the paths keep from the blocks.
+    pub obstacle_margin: Coord,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -55,6 +183,9 @@ pub struct BlockConfig {
     #[serde(default)]
     pub vulnerability: VulnerabilityStats,
     pub explosion: Option<ExplosionConfig>,
+    /// How quickly the block catches fire from the burning neighbours, 0 if it does not burn.
+    #[serde(default)]
+    pub flammability: R32,
 }
 
 fn default_weight() -> R32 {
@@ -81,7 +212,11 @@ pub struct PlayerConfig {
     pub dash_burst: Coord,
     pub acceleration: Coord,
     pub hp: Hp,
-    pub gun: GunConfig,
+    /// Immunity to damage after getting hit (in seconds).
+    pub invulnerability_time: Time,
+    /// Names of the weapons in `assets/weapons` the player starts with.
+    /// The first one is active.
+    pub weapons: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -101,6 +236,57 @@ pub struct BarrelStateConfig {
     pub self_explosion_strength: Coord,
     pub body: BodyConfig,
     pub gasoline: GasolineConfig,
+    /// Ramming into the blocks damages them.
+    #[serde(default)]
+    pub ramming: Option<RammingConfig>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RammingConfig {
+    /// Impacts slower than that only bounce off.
+    pub min_speed: Coord,
+    /// Damage per unit of the impact speed, scaled by the block's physical vulnerability.
+    pub damage_per_speed: Hp,
+    /// Fraction of the speed kept when plowing through a block the impact destroys.
+    /// Blocks that survive the impact stop the barrel.
+    pub plow_speed: R32,
+    /// Debris particles per point of damage dealt.
+    pub debris: R32,
+    pub barrel_impact: BarrelImpact,
+}
+
+/// What happens to the barrel blocks rammed into.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum BarrelImpact {
+    /// Explode on the spot.
+    Detonate,
+    /// Fly in the direction of the impact and explode on landing.
+    Launch {
+        distance: Coord,
+        speed: Coord,
+        arc_height: Coord,
+    },
+}
+
+impl BarrelImpact {
+    fn validate(&self) -> anyhow::Result<()> {
+        if let Self::Launch {
+            distance, speed, ..
+        } = *self
+        {
+            anyhow::ensure!(
+                speed > Coord::ZERO,
+                "Launch speed must be positive, got {}",
+                speed
+            );
+            anyhow::ensure!(
+                distance > Coord::ZERO,
+                "Launch distance must be positive, got {}",
+                distance
+            );
+        }
+        Ok(())
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -108,13 +294,72 @@ pub struct GasolineConfig {
     /// Whether gasoline dripping can be controlled (turned on/off).
     pub can_control: bool,
     pub cost: R32,
-    pub lifetime: Time,
     pub distance_period: Coord,
-    pub ignite_timer: Time,
-    pub fire_radius: Coord,
+    /// Radius of every drop.
+    pub radius: Coord,
+    /// Amount of gasoline every drop adds to the cells it covers.
+    pub amount: R32,
+    /// Explosion of the sparse cells catching fire, see [GasolineFieldConfig::explosion_spacing].
     pub explosion: ExplosionConfig,
-    pub shape: Shape,
+    /// Fire of the burning gasoline.
+    /// Spreads to the neighbouring cells after `spread_time` and burns out after `lifetime`.
     pub fire: FireConfig,
+    pub field: GasolineFieldConfig,
+    /// Actors walking through the gasoline get soaked in it.
+    #[serde(default)]
+    pub soak: Option<SoakConfig>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GasolineFieldConfig {
+    pub cell_size: Coord,
+    /// The gasoline above that amount flows out to the neighbouring cells.
+    pub pool_depth: R32,
+    /// Fraction of the excess gasoline flowing out every second.
+    pub spread_rate: R32,
+    /// Amount of gasoline evaporating from every cell per second.
+    pub evaporation: R32,
+    /// Cells with less gasoline do not catch fire.
+    pub min_density: R32,
+    /// Distance between the cells that explode when catching fire.
+    pub explosion_spacing: Coord,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SoakConfig {
+    /// How long the actors stay soaked after leaving the gasoline.
+    pub duration: Time,
+    /// Fire damage multiplier of the soaked actors.
+    pub fire_multiplier: R32,
+    /// Actors get soaked in the cells with at least that much gasoline.
+    pub min_density: R32,
+    /// Soaked actors catch this fire from explosions and spread it to whoever they touch.
+    pub fire: FireConfig,
+    pub drip: DripConfig,
+}
+
+/// Gasoline dripping from the soaked actors.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DripConfig {
+    /// Drops per second.
+    pub rate: R32,
+    pub radius: Coord,
+    /// Amount of gasoline every drop adds to the cells it covers.
+    pub amount: R32,
+}
+
+impl SoakConfig {
+    /// The oil soaking the actors.
+    pub fn soaked(&self, source: DamageSource) -> StatusEffect {
+        StatusEffect {
+            kind: StatusKind::OilSoaked {
+                fire_multiplier: self.fire_multiplier,
+            },
+            duration: self.duration,
+            stacking: Stacking::Refresh,
+            source: Some(source),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -124,13 +369,39 @@ pub struct ExplosionConfig {
     pub damage: Hp,
     #[serde(default)]
     pub ignite_gasoline: bool,
-    pub ignite: Option<OnFire>,
+    /// Status effects applied to everything caught in the explosion.
+    #[serde(default)]
+    pub statuses: Vec<StatusEffect>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FireConfig {
+    /// How long the targets keep burning after catching fire.
     pub duration: Time,
     pub damage_per_second: Hp,
+    /// How long the fire keeps burning on the ground.
+    pub lifetime: Time,
+    /// Time in contact after which the fire spreads to a flammable neighbour.
+    pub spread_time: Time,
+    /// Max distance between the fire and the neighbours it spreads to.
+    pub spread_distance: Coord,
+    /// Once the fire goes out, the target does not catch fire again for that time.
+    pub burnt_out_time: Time,
+}
+
+impl FireConfig {
+    /// The fire burning the targets.
+    pub fn burning(&self, source: Option<DamageSource>) -> StatusEffect {
+        StatusEffect {
+            kind: StatusKind::Burning {
+                damage_per_second: self.damage_per_second,
+                burnt_out: self.burnt_out_time,
+            },
+            duration: self.duration,
+            stacking: Stacking::Refresh,
+            source,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -139,6 +410,16 @@ pub struct GunConfig {
     pub shot_delay: Time,
     pub shot: ShotConfig,
     pub recoil: Coord,
+    /// Unlimited ammo if not set.
+    #[serde(default)]
+    pub ammo: Option<AmmoConfig>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct AmmoConfig {
+    /// Shots before the gun has to reload.
+    pub capacity: usize,
+    pub reload_time: Time,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -159,6 +440,46 @@ pub struct ProjectileConfig {
     #[serde(default)]
     pub kind: ProjectileKind,
     pub knockback: Coord,
+    /// Status effects applied to the target on hit.
+    #[serde(default)]
+    pub statuses: Vec<StatusEffect>,
+    /// Times the projectile bounces off the blocks instead of breaking.
+    #[serde(default)]
+    pub bounces: usize,
+    /// Number of actors the projectile passes through before breaking.
+    #[serde(default)]
+    pub pierce: usize,
+    /// Speed multipliers evenly spread over the lifetime, interpolated in between.
+    /// Constant speed if empty.
+    #[serde(default)]
+    pub speed_curve: Vec<R32>,
+    /// Throw the projectile in an arc to the aimed point instead of shooting it.
+    #[serde(default)]
+    pub throw: Option<ThrowConfig>,
+}
+
+/// A thrown projectile flies over everything and only acts once it lands.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ThrowConfig {
+    /// Peak height of the arc, only visual.
+    pub arc_height: Coord,
+    #[serde(default)]
+    pub explosion: Option<ExplosionConfig>,
+    /// Sets the gasoline within the radius on fire.
+    #[serde(default)]
+    pub ignite_radius: Option<Coord>,
+    /// Fire left on the ground.
+    #[serde(default)]
+    pub fire: Option<FirePuddle>,
+    /// Gasoline spilled on the ground.
+    #[serde(default)]
+    pub spill: Option<GasolineSpill>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FirePuddle {
+    pub radius: Coord,
+    pub fire: FireConfig,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -168,12 +489,31 @@ pub struct EnemyConfig {
     pub acceleration: Coord,
     pub hp: Hp,
     pub ai: ActorAI,
+    #[serde(default)]
+    pub steering: SteeringConfig,
     pub kind: ActorKind,
+    #[serde(default)]
     pub gun: Option<GunConfig>,
+    /// Name of a weapon in `assets/weapons`, used instead of the `gun`.
+    #[serde(default)]
+    pub weapon: Option<String>,
     #[serde(default)]
     pub stops_barrel: bool,
 }
 
+impl EnemyConfig {
+    /// Replace the weapon name with the gun from the weapons.
+    fn resolve_weapon(mut self, weapons: &HashMap<String, GunConfig>) -> anyhow::Result<Self> {
+        if let Some(name) = &self.weapon {
+            let gun = weapons
+                .get(name)
+                .ok_or_else(|| anyhow::anyhow!("Weapon {:?} not found", name))?;
+            self.gun = Some(gun.clone());
+        }
+        Ok(self)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BodyConfig {
     pub shape: Shape,
@@ -189,11 +529,20 @@ impl BodyConfig {
 
 impl Config {
     pub async fn load(path: impl AsRef<std::path::Path>) -> anyhow::Result<Self> {
-        crate::util::load_file(path).await
+        let config: Self = crate::util::load_file(path).await?;
+        if let Some(ramming) = &config.player.barrel_state.ramming {
+            ramming
+                .barrel_impact
+                .validate()
+                .context("in the barrel ramming config")?;
+        }
+        Ok(config)
     }
 
+    /// Load the enemies, with their weapons resolved from the `weapons`.
     pub async fn load_enemies(
         path: impl AsRef<std::path::Path>,
+        weapons: &HashMap<String, GunConfig>,
     ) -> anyhow::Result<HashMap<String, EnemyConfig>> {
         let path = path.as_ref();
         log::debug!("Loading folder {:?}", path);
@@ -204,8 +553,41 @@ impl Config {
         for name in list {
             let enemy: EnemyConfig =
                 crate::util::load_file(&path.join(&name).with_extension("ron")).await?;
+            let enemy = enemy
+                .resolve_weapon(weapons)
+                .with_context(|| format!("when loading enemy {:?}", name))?;
             enemies.insert(name, enemy);
         }
+
+        for (name, enemy) in &enemies {
+            if let ActorAI::Summoner { minions, .. } = &enemy.ai {
+                for minion in minions {
+                    anyhow::ensure!(
+                        enemies.contains_key(minion),
+                        "Minion {:?} of enemy {:?} not found",
+                        minion,
+                        name
+                    );
+                }
+            }
+        }
         Ok(enemies)
     }
+
+    pub async fn load_weapons(
+        path: impl AsRef<std::path::Path>,
+    ) -> anyhow::Result<HashMap<String, GunConfig>> {
+        let path = path.as_ref();
+        log::debug!("Loading folder {:?}", path);
+
+        let list: Vec<String> = crate::util::load_file(&path.join("_list.ron")).await?;
+
+        let mut weapons = HashMap::new();
+        for name in list {
+            let weapon: GunConfig =
+                crate::util::load_file(&path.join(&name).with_extension("ron")).await?;
+            weapons.insert(name, weapon);
+        }
+        Ok(weapons)
+    }
 }
@@ -12,6 +12,11 @@ pub struct Controls {
     pub transform: Vec<EventKey>,
     pub barrel_dash: Vec<EventKey>,
     pub gas: Vec<EventKey>,
+    pub next_weapon: Vec<EventKey>,
+    pub previous_weapon: Vec<EventKey>,
+    pub reload: Vec<EventKey>,
+    pub quicksave: Vec<EventKey>,
+    pub quickload: Vec<EventKey>,
 }
 
 impl Controls {
@@ -1,3 +1,4 @@
+pub mod bosses;
 pub mod config;
 pub mod controls;
 pub mod theme;
@@ -17,7 +17,12 @@ pub struct Theme {
     pub health_fg_player: Color,
     pub health_fg_enemy: Color,
     pub whiteish: Color,
+    /// Aim lines and dash warnings of the enemies.
+    pub telegraph: Color,
+    /// Pieces of the blocks broken by ramming.
+    pub debris: Color,
     pub pickups: PickUpsTheme,
+    pub status: StatusTheme,
 }
 
 pub type Palette = HashMap<String, Color>;
@@ -27,6 +32,14 @@ pub struct PickUpsTheme {
     pub heal: Color,
 }
 
+/// Particles emitted by actors and blocks under status effects.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct StatusTheme {
+    pub poison: Color,
+    pub frost: Color,
+    pub oil: Color,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct LevelTheme {
     pub background: Vec<String>,
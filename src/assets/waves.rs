@@ -2,7 +2,7 @@ use super::*;
 
 use crate::model::{Coord, Time};
 
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct WavesConfig {
@@ -13,6 +13,9 @@ pub struct WavesConfig {
     /// The radius for the spawn circle, in which the all enemies from a wave will spawn.
     pub spawn_circle_radius: Coord,
     pub infinite_waves_until_boss: usize,
+    /// Name of the boss that ends the run, spawned after the infinite waves.
+    #[serde(default)]
+    pub boss: Option<String>,
     pub infinite_wave: InfiniteWaveConfig,
     pub waves: VecDeque<WaveConfig>,
 }
@@ -28,7 +31,8 @@ pub struct InfiniteWaveConfig {
     /// Delay the first enemy spawn.
     pub wave_delay: Time,
     /// List of enemy names.
-    pub enemies: HashMap<String, InfiniteEnemyConfig>,
+    /// Ordered, so that picking an enemy with a seeded rng is reproducible.
+    pub enemies: BTreeMap<String, InfiniteEnemyConfig>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
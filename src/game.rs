@@ -1,6 +1,7 @@
 use crate::{
     assets::{
-        config::{Config, EnemyConfig, LevelConfig},
+        bosses::BossConfig,
+        config::{Config, EnemyConfig, GunConfig, LevelConfig},
         controls::Controls,
         theme::Theme,
         waves::WavesConfig,
@@ -9,11 +10,71 @@ use crate::{
     model::*,
     prelude::*,
     render::GameRender,
+    replay::{run_path, ConfigHashes, Replay, ReplayPlayer, ReplayRecorder, ReplayTick},
+    util::FixedTimestep,
 };
 
 #[derive(Debug)]
 pub enum GameEvent {
-    PlaySound { sound: Sound, volume: R32 },
+    PlaySound {
+        sound: Sound,
+        volume: R32,
+    },
+    /// The pair of actors started touching.
+    ContactEntered {
+        pair: (Id, Id),
+    },
+    /// The pair of actors stopped touching.
+    ContactExited {
+        pair: (Id, Id),
+    },
+    EnemyKilled {
+        kind: ActorKind,
+        position: Position,
+        /// The last damage the enemy took, if any.
+        source: Option<DamageSource>,
+    },
+    /// Any damage dealt to an actor or a block.
+    DamageDealt {
+        target: DamageTarget,
+        position: Position,
+        damage: Hp,
+        damage_type: DamageType,
+        source: DamageSource,
+    },
+    PlayerDamaged {
+        damage: Hp,
+        damage_type: DamageType,
+        source: DamageSource,
+    },
+    WaveStarted {
+        wave: usize,
+    },
+    WaveCleared {
+        wave: usize,
+    },
+    BossSpawned,
+    /// The boss entered the next phase of the fight.
+    BossPhaseChanged {
+        /// Number of the phase, starting from 1.
+        phase: usize,
+        name: String,
+        position: Position,
+    },
+    PickupCollected {
+        kind: PickUpKind,
+        position: Position,
+    },
+    BlockDestroyed {
+        kind: BlockKind,
+        position: Position,
+    },
+    GasolineIgnited {
+        position: Position,
+    },
+    PlayerStateChanged {
+        state: PlayerState,
+    },
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -27,11 +88,25 @@ pub struct Game {
     assets: Rc<Assets>,
     framebuffer_size: vec2<usize>,
     delta_time: Time,
+    timestep: FixedTimestep,
     screen_texture: ugli::Texture,
     controls: Controls,
     can_shoot: bool,
+    /// Actions triggered by events since the last tick.
+    queued_actions: Vec<PlayerAction>,
     render: GameRender,
     model: Model,
+    /// Seed specified by the user, used for every restart.
+    seed: Option<u64>,
+    /// Path to record the replay of the first run to, see [run_path].
+    record_path: Option<std::path::PathBuf>,
+    /// Number of the current run since the start of the game.
+    run: usize,
+    recorder: Option<ReplayRecorder>,
+    /// When playing back a replay, the player's input is ignored.
+    playback: Option<ReplayPlayer>,
+    /// Path to save the snapshots of the model to.
+    quicksave_path: std::path::PathBuf,
     master_volume: f32,
     // music_volume: f32,
     explosion_timeout: f32,
@@ -47,17 +122,43 @@ impl Game {
         theme: Theme,
         controls: Controls,
         enemies: HashMap<String, EnemyConfig>,
+        bosses: HashMap<String, BossConfig>,
+        weapons: HashMap<String, GunConfig>,
         waves: WavesConfig,
+        seed: Option<u64>,
+        record_path: Option<std::path::PathBuf>,
+        replay: Option<Replay>,
+        quicksave_path: std::path::PathBuf,
     ) -> Self {
         geng.window().set_cursor_type(geng::CursorType::None);
         let mut effect = assets.sounds.music.play();
         effect.set_volume(0.07);
 
+        let seed = replay.as_ref().map(|replay| replay.seed).or(seed);
+        let model = Model::new(
+            theme.clone(),
+            config,
+            level,
+            enemies,
+            bosses,
+            weapons,
+            waves,
+            seed.unwrap_or_else(|| thread_rng().gen()),
+        );
+        log::info!("Starting the game with seed {}", model.seed);
+
+        if let Some(replay) = &replay {
+            if replay.hashes != ConfigHashes::new(&model) {
+                log::warn!("The replay was recorded with different configs, it might desync");
+            }
+        }
+
         Self {
             geng: geng.clone(),
             assets: assets.clone(),
             framebuffer_size: vec2(1, 1),
             delta_time: Time::new(1.0),
+            timestep: FixedTimestep::new(model.config.tick_rate),
             screen_texture: {
                 let mut texture =
                     ugli::Texture::new_with(geng.ugli(), crate::SCREEN_SIZE, |_| Rgba::BLACK);
@@ -66,16 +167,25 @@ impl Game {
             },
             controls,
             can_shoot: true,
-            model: Model::new(theme.clone(), config, level, enemies, waves),
-            render: GameRender::new(geng, assets, theme),
+            queued_actions: Vec::new(),
+            render: GameRender::new(geng, assets, theme, model.seed),
+            recorder: record_path
+                .clone()
+                .map(|path| ReplayRecorder::new(path, &model)),
+            playback: replay.map(ReplayPlayer::new),
+            model,
+            seed,
+            record_path,
+            run: 0,
+            quicksave_path,
             master_volume: 0.5,
             // music_volume: 1.0,
             explosion_timeout: 0.0,
         }
     }
 
-    fn update_player(&mut self, event: &geng::Event) {
-        let player = &mut self.model.player;
+    /// Poll the current input state of the player.
+    fn player_input(&self) -> PlayerInput {
         let window = self.geng.window();
 
         // Change player velocity based on input.
@@ -93,24 +203,44 @@ impl Game {
             player_direction.x -= 1.0;
         }
 
-        // Assign normalized
-        player.input.direction = player_direction.normalize_or_zero().as_r32();
-
-        // Aim
-        player.input.aim_at = self.model.camera.cursor_pos_world();
+        PlayerInput {
+            // Assign normalized
+            direction: player_direction.normalize_or_zero().as_r32(),
+            // Aim
+            aim_at: self.model.camera.cursor_pos_world(),
+            // Drip gasoline
+            drip_gas: key_utils::is_key_pressed(window, &self.controls.gas),
+        }
+    }
 
-        // Drip gasoline
-        player.input.drip_gas = key_utils::is_key_pressed(window, &self.controls.gas);
+    fn update_player(&mut self, event: &geng::Event) {
+        if self.playback.is_some() {
+            // The actions come from the replay
+            return;
+        }
 
         // Transform state
         if key_utils::is_event_press(event, &self.controls.transform) {
-            self.model.player_action(PlayerAction::SwitchState);
+            self.queued_actions.push(PlayerAction::SwitchState);
+        }
+
+        // Weapons
+        if key_utils::is_event_press(event, &self.controls.next_weapon) {
+            self.queued_actions
+                .push(PlayerAction::SwitchWeapon { offset: 1 });
+        }
+        if key_utils::is_event_press(event, &self.controls.previous_weapon) {
+            self.queued_actions
+                .push(PlayerAction::SwitchWeapon { offset: -1 });
+        }
+        if key_utils::is_event_press(event, &self.controls.reload) {
+            self.queued_actions.push(PlayerAction::Reload);
         }
 
         // Barrel dash
         if let PlayerState::Barrel { .. } = self.model.player.state {
             if key_utils::is_event_press(event, &self.controls.barrel_dash) {
-                self.model.player_action(PlayerAction::BarrelDash);
+                self.queued_actions.push(PlayerAction::BarrelDash);
                 self.can_shoot = false;
             }
         } else if key_utils::is_event_release(event, &self.controls.barrel_dash) {
@@ -118,9 +248,116 @@ impl Game {
         };
     }
 
+    /// Collect the input for the next tick,
+    /// either from the player or from the replay being played back.
+    fn next_tick_input(&mut self) -> Option<ReplayTick> {
+        if let Some(playback) = &mut self.playback {
+            return playback.next_tick();
+        }
+
+        let mut actions = std::mem::take(&mut self.queued_actions);
+        let window = self.geng.window();
+        if self.can_shoot && key_utils::is_key_pressed(window, &self.controls.shoot) {
+            let target_pos = self.model.camera.cursor_pos_world();
+            actions.push(PlayerAction::Shoot { target_pos });
+        }
+
+        Some(ReplayTick {
+            input: self.player_input(),
+            actions,
+        })
+    }
+
+    /// Simulate a single tick of the model.
+    fn tick(&mut self) {
+        let Some(tick) = self.next_tick_input() else {
+            // The replay is over
+            return;
+        };
+
+        self.model.player.input = tick.input.clone();
+        for action in tick.actions.iter().cloned() {
+            self.model.player_action(action);
+        }
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(tick);
+        }
+
+        for event in self.model.update(self.timestep.tick) {
+            self.handle_game_event(event);
+        }
+
+        if self
+            .model
+            .actors
+            .health
+            .get(self.model.player.actor)
+            .is_none()
+        {
+            // The run is over
+            if let Some(recorder) = &mut self.recorder {
+                recorder.save();
+            }
+        }
+    }
+
     /// Restart the game
     fn reset(&mut self) {
-        self.model.reset();
+        if let Some(recorder) = &mut self.recorder {
+            recorder.save();
+        }
+        self.queued_actions.clear();
+
+        let seed = match &mut self.playback {
+            Some(playback) => {
+                playback.restart();
+                playback.replay.seed
+            }
+            None => self.seed.unwrap_or_else(|| thread_rng().gen()),
+        };
+        log::info!("Restarting with seed {}", seed);
+        self.model.reset(seed);
+
+        self.run += 1;
+        if let Some(path) = &self.record_path {
+            let path = run_path(path, self.run);
+            self.recorder = Some(ReplayRecorder::new(path, &self.model));
+        }
+    }
+
+    /// Save a snapshot of the model to disk.
+    fn quicksave(&mut self) {
+        let Some(snapshot) = self.model.snapshot() else {
+            log::warn!("Cannot save the game after the run is over");
+            return;
+        };
+        match snapshot.save(&self.quicksave_path) {
+            Ok(()) => log::info!("Saved the game to {:?}", self.quicksave_path),
+            Err(err) => log::error!("Failed to save the game: {:?}", err),
+        }
+    }
+
+    /// Restore the model from a snapshot on disk.
+    fn quickload(&mut self) {
+        if self.playback.is_some() {
+            log::warn!("Cannot load the game while playing back a replay");
+            return;
+        }
+
+        let snapshot = match ModelSnapshot::load(&self.quicksave_path) {
+            Ok(snapshot) => snapshot,
+            Err(err) => {
+                log::error!("Failed to load the game: {:?}", err);
+                return;
+            }
+        };
+        self.model = Model::from_snapshot(snapshot);
+        self.queued_actions.clear();
+        log::info!("Loaded the game from {:?}", self.quicksave_path);
+
+        if self.recorder.take().is_some() {
+            log::warn!("Replays cannot include loading the game, stopped recording at the load");
+        }
     }
 
     fn handle_game_event(&mut self, event: GameEvent) {
@@ -140,6 +377,23 @@ impl Game {
                 let mut sound = sound.play();
                 sound.set_volume((volume * volume_mult).into());
             }
+            GameEvent::BossPhaseChanged { .. } => {
+                let mut sound = self.assets.sounds.explosion.play();
+                sound.set_volume((self.master_volume * 0.3).into());
+            }
+            GameEvent::WaveStarted { wave } => log::info!("Wave {} started", wave),
+            GameEvent::WaveCleared { wave } => log::info!("Wave {} cleared", wave),
+            GameEvent::BossSpawned => log::info!("The boss has spawned"),
+            // No feedback for these yet, listed so that new events do not get missed
+            GameEvent::ContactEntered { .. }
+            | GameEvent::ContactExited { .. }
+            | GameEvent::EnemyKilled { .. }
+            | GameEvent::DamageDealt { .. }
+            | GameEvent::PlayerDamaged { .. }
+            | GameEvent::PickupCollected { .. }
+            | GameEvent::BlockDestroyed { .. }
+            | GameEvent::GasolineIgnited { .. }
+            | GameEvent::PlayerStateChanged { .. } => {}
         }
     }
 }
@@ -157,15 +411,10 @@ impl geng::State for Game {
         let delta_time = Time::new(delta_time);
         self.delta_time = delta_time;
 
-        let window = self.geng.window();
-        if self.can_shoot && key_utils::is_key_pressed(window, &self.controls.shoot) {
-            let target_pos = self.model.camera.cursor_pos_world();
-            self.model.player_action(PlayerAction::Shoot { target_pos });
-        }
-
-        for event in self.model.update(delta_time) {
-            self.handle_game_event(event);
+        for _ in 0..self.timestep.advance(delta_time) {
+            self.tick();
         }
+        self.model.camera.interpolation = self.timestep.alpha();
     }
 
     fn draw(&mut self, framebuffer: &mut ugli::Framebuffer) {
@@ -218,6 +467,13 @@ impl geng::State for Game {
             }
         }
 
+        if key_utils::is_event_press(&event, &self.controls.quicksave) {
+            self.quicksave();
+        }
+        if key_utils::is_event_press(&event, &self.controls.quickload) {
+            self.quickload();
+        }
+
         self.update_player(&event);
     }
 }
@@ -230,10 +486,18 @@ pub fn run(geng: &Geng, opts: crate::Opts) -> impl geng::State {
             let assets = Assets::load(manager).await.unwrap();
             let config = Config::load(&opts.config).await.unwrap();
             let level: LevelConfig = crate::util::load_file(&opts.level).await.unwrap();
-            let enemies = Config::load_enemies(&opts.enemies).await.unwrap();
+            let weapons = Config::load_weapons(&opts.weapons).await.unwrap();
+            let enemies = Config::load_enemies(&opts.enemies, &weapons).await.unwrap();
             let waves = WavesConfig::load(&opts.waves).await.unwrap();
+            let bosses = BossConfig::load_all(&opts.bosses, &enemies, &waves)
+                .await
+                .unwrap();
             let theme = Theme::load(&opts.theme).await.unwrap();
             let controls = Controls::load(&opts.controls).await.unwrap();
+            let replay = match &opts.replay {
+                Some(path) => Some(Replay::load(path).await.unwrap()),
+                None => None,
+            };
             Game::new(
                 &geng,
                 &Rc::new(assets),
@@ -242,7 +506,13 @@ pub fn run(geng: &Geng, opts: crate::Opts) -> impl geng::State {
                 theme,
                 controls,
                 enemies,
+                bosses,
+                weapons,
                 waves,
+                opts.seed,
+                opts.record,
+                replay,
+                opts.quicksave,
             )
         }
     };
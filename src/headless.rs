@@ -0,0 +1,185 @@
+use crate::{
+    assets::{
+        bosses::BossConfig,
+        config::{Config, EnemyConfig, GunConfig, LevelConfig},
+        theme::Theme,
+        waves::WavesConfig,
+    },
+    model::*,
+    prelude::*,
+    util::FixedTimestep,
+};
+
+#[derive(clap::Args, Clone)]
+pub struct HeadlessOpts {
+    /// Number of runs to simulate. Each run uses the next seed after the previous one.
+    #[clap(long, default_value = "1")]
+    runs: usize,
+    /// Simulation ticks per second, the `tick_rate` of the config by default.
+    #[clap(long)]
+    tick_rate: Option<f32>,
+    /// Stop the run after that much simulated time (in seconds).
+    #[clap(long, default_value = "600.0")]
+    max_time: f32,
+    /// How the player is controlled.
+    #[clap(long, value_enum, default_value = "shooter")]
+    bot: Bot,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy)]
+pub enum Bot {
+    /// Does nothing.
+    Idle,
+    /// Stands still and shoots at the closest enemy.
+    Turret,
+    /// Keeps distance from the closest enemy while shooting at it.
+    Shooter,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RunSummary {
+    pub seed: u64,
+    pub time_survived: Time,
+    pub waves_reached: usize,
+    pub kills: usize,
+}
+
+struct Configs {
+    config: Config,
+    level: LevelConfig,
+    enemies: HashMap<String, EnemyConfig>,
+    bosses: HashMap<String, BossConfig>,
+    weapons: HashMap<String, GunConfig>,
+    waves: WavesConfig,
+    theme: Theme,
+}
+
+pub fn run(opts: &crate::Opts, headless: &HeadlessOpts) -> anyhow::Result<()> {
+    let configs = futures::executor::block_on(load_configs(opts))?;
+
+    let base_seed = opts.seed.unwrap_or_else(|| thread_rng().gen());
+    let tick_rate = headless
+        .tick_rate
+        .map_or(configs.config.tick_rate, R32::new);
+    let max_time = Time::new(headless.max_time);
+
+    let mut summaries = Vec::with_capacity(headless.runs);
+    for i in 0..headless.runs {
+        let seed = base_seed.wrapping_add(i as u64);
+        let summary = simulate(&configs, seed, tick_rate, max_time, headless.bot);
+        println!(
+            "seed {}: survived {:.1} sec, reached wave {}, killed {}",
+            summary.seed,
+            summary.time_survived.as_f32(),
+            summary.waves_reached,
+            summary.kills
+        );
+        summaries.push(summary);
+    }
+
+    if summaries.len() > 1 {
+        let runs = summaries.len() as f32;
+        let time = summaries
+            .iter()
+            .map(|summary| summary.time_survived.as_f32())
+            .sum::<f32>()
+            / runs;
+        let waves = summaries
+            .iter()
+            .map(|summary| summary.waves_reached as f32)
+            .sum::<f32>()
+            / runs;
+        let kills = summaries
+            .iter()
+            .map(|summary| summary.kills as f32)
+            .sum::<f32>()
+            / runs;
+        println!(
+            "average over {} runs: survived {:.1} sec, reached wave {:.1}, killed {:.1}",
+            summaries.len(),
+            time,
+            waves,
+            kills
+        );
+    }
+
+    Ok(())
+}
+
+async fn load_configs(opts: &crate::Opts) -> anyhow::Result<Configs> {
+    let weapons = Config::load_weapons(&opts.weapons).await?;
+    let enemies = Config::load_enemies(&opts.enemies, &weapons).await?;
+    let waves = WavesConfig::load(&opts.waves).await?;
+    Ok(Configs {
+        config: Config::load(&opts.config).await?,
+        level: crate::util::load_file(&opts.level).await?,
+        bosses: BossConfig::load_all(&opts.bosses, &enemies, &waves).await?,
+        enemies,
+        weapons,
+        waves,
+        theme: Theme::load(&opts.theme).await?,
+    })
+}
+
+fn simulate(configs: &Configs, seed: u64, tick_rate: R32, max_time: Time, bot: Bot) -> RunSummary {
+    let mut model = Model::new(
+        configs.theme.clone(),
+        configs.config.clone(),
+        configs.level.clone(),
+        configs.enemies.clone(),
+        configs.bosses.clone(),
+        configs.weapons.clone(),
+        configs.waves.clone(),
+        seed,
+    );
+
+    // Same fixed ticks as the game, with every frame lasting exactly one tick
+    let mut timestep = FixedTimestep::new(tick_rate);
+    // Stop once the player is dead
+    while model.time < max_time && model.actors.health.get(model.player.actor).is_some() {
+        for _ in 0..timestep.advance(timestep.tick) {
+            control_bot(&mut model, bot);
+            model.update(timestep.tick);
+        }
+    }
+
+    RunSummary {
+        seed,
+        time_survived: model.time_alive,
+        waves_reached: model.wave_manager.wave_number,
+        kills: model.kills,
+    }
+}
+
+fn control_bot(model: &mut Model, bot: Bot) {
+    let Some(&player_pos) = model.actors.body.collider.position.get(model.player.actor) else {
+        return;
+    };
+
+    let closest_enemy = query!(model.actors, (&body.collider.position, &fraction))
+        .filter(|(_, fraction)| **fraction == Fraction::Enemy)
+        .map(|(&position, _)| position)
+        .min_by_key(|&position| player_pos.distance(position));
+
+    model.player.input.direction = vec2::ZERO;
+    model.player.input.drip_gas = false;
+
+    let Some(target_pos) = closest_enemy else {
+        return;
+    };
+    model.player.input.aim_at = target_pos;
+
+    match bot {
+        Bot::Idle => {}
+        Bot::Turret => {
+            model.player_action(PlayerAction::Shoot { target_pos });
+        }
+        Bot::Shooter => {
+            let delta = player_pos.delta_to(target_pos);
+            if delta.len() < r32(30.0) {
+                model.player.input.direction = -delta.normalize_or_zero();
+            }
+            model.player_action(PlayerAction::Shoot { target_pos });
+        }
+    }
+}
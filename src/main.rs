@@ -18,9 +18,13 @@ use dynamic_linking;
 
 mod assets;
 mod game;
+mod headless;
 mod menu;
 mod model;
 mod render;
+mod replay;
+#[cfg(test)]
+mod tests;
 mod util;
 
 mod prelude {
@@ -49,22 +53,51 @@ pub struct Opts {
     level: std::path::PathBuf,
     #[clap(long, default_value = "assets/enemies/")]
     enemies: std::path::PathBuf,
+    #[clap(long, default_value = "assets/bosses/")]
+    bosses: std::path::PathBuf,
+    #[clap(long, default_value = "assets/weapons/")]
+    weapons: std::path::PathBuf,
     #[clap(long, default_value = "assets/waves.ron")]
     waves: std::path::PathBuf,
     #[clap(long, default_value = "assets/theme.toml")]
     theme: std::path::PathBuf,
     #[clap(long, default_value = "assets/controls.ron")]
     controls: std::path::PathBuf,
+    /// Seed for the simulation. A random one is picked if not specified.
+    #[clap(long)]
+    seed: Option<u64>,
+    /// Record the input of every run to the file.
+    #[clap(long)]
+    record: Option<std::path::PathBuf>,
+    /// Play back a recorded run from the file.
+    #[clap(long)]
+    replay: Option<std::path::PathBuf>,
+    /// File to quicksave the game to and quickload it from.
+    #[clap(long, default_value = "quicksave.ron")]
+    quicksave: std::path::PathBuf,
+    #[clap(subcommand)]
+    command: Option<Command>,
     #[clap(flatten)]
     geng: geng::CliArgs,
 }
 
+#[derive(clap::Subcommand, Clone)]
+enum Command {
+    /// Run the simulation without a window and print a summary.
+    Headless(headless::HeadlessOpts),
+}
+
 fn main() {
     logger::init();
     geng::setup_panic_handler();
 
     let opts: Opts = clap::Parser::parse();
 
+    if let Some(Command::Headless(headless_opts)) = &opts.command {
+        headless::run(&opts, headless_opts).unwrap();
+        return;
+    }
+
     let mut geng_options = geng::ContextOptions::default();
     geng_options.window.title = "Horns of Combustion".to_string();
     geng_options.window.size = Some(SCREEN_SIZE);
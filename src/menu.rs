@@ -1,6 +1,7 @@
 use crate::{
     assets::{
-        config::{Config, EnemyConfig, LevelConfig},
+        bosses::BossConfig,
+        config::{Config, EnemyConfig, GunConfig, LevelConfig},
         theme::Theme,
         waves::WavesConfig,
         Assets,
@@ -8,6 +9,7 @@ use crate::{
     model::{Model, Time},
     prelude::*,
     render::GameRender,
+    util::FixedTimestep,
 };
 
 const BUTTON_SIZE: vec2<f32> = vec2(5.0, 2.0);
@@ -36,6 +38,7 @@ pub struct StartMenu {
     model: Model,
     game_texture: ugli::Texture,
     delta_time: Time,
+    timestep: FixedTimestep,
     cursor_pos: vec2<f32>,
     play_button: Aabb2<f32>,
     exit_button: Aabb2<f32>,
@@ -54,8 +57,12 @@ impl StartMenu {
         level: LevelConfig,
         theme: Theme,
         enemies: HashMap<String, EnemyConfig>,
+        bosses: HashMap<String, BossConfig>,
+        weapons: HashMap<String, GunConfig>,
         waves: WavesConfig,
     ) -> Self {
+        let seed = opts.seed.unwrap_or_else(|| thread_rng().gen());
+        let timestep = FixedTimestep::new(config.tick_rate);
         Self {
             geng: geng.clone(),
             assets: assets.clone(),
@@ -68,16 +75,19 @@ impl StartMenu {
             },
             framebuffer_size: vec2(1, 1),
             cursor_pos: vec2::ZERO,
-            render: GameRender::new(geng, assets, theme.clone()),
+            render: GameRender::new(geng, assets, theme.clone(), seed),
             model: Model::new(
                 theme,
                 config,
                 level,
                 enemies,
+                bosses,
+                weapons,
                 WavesConfig {
                     infinite_waves_until_boss: usize::MAX,
                     ..waves
                 },
+                seed,
             ),
             play_button: Aabb2::point(vec2(0.0, -1.0)).extend_symmetric(BUTTON_SIZE / 2.0),
             exit_button: Aabb2::point(vec2(0.0, -3.5)).extend_symmetric(BUTTON_SIZE / 2.0),
@@ -94,6 +104,7 @@ impl StartMenu {
                 texture
             },
             delta_time: Time::ONE,
+            timestep,
             animation_frame: 0,
             next_frame: assets.sprites.game_logo.first().unwrap().duration,
         }
@@ -155,7 +166,10 @@ impl geng::State for StartMenu {
         }
 
         self.delta_time = Time::new(delta_time);
-        self.model.update(self.delta_time);
+        for _ in 0..self.timestep.advance(self.delta_time) {
+            self.model.update(self.timestep.tick);
+        }
+        self.model.camera.interpolation = self.timestep.alpha();
         if self.model.time - self.model.time_alive > Time::new(5.0) {
             self.model.revive();
         }
@@ -255,8 +269,12 @@ pub fn run(geng: &Geng, opts: crate::Opts) -> impl geng::State {
             let assets = Assets::load(manager).await.unwrap();
             let config = Config::load(&opts.config).await.unwrap();
             let level: LevelConfig = crate::util::load_file(&opts.level).await.unwrap();
-            let enemies = Config::load_enemies(&opts.enemies).await.unwrap();
+            let weapons = Config::load_weapons(&opts.weapons).await.unwrap();
+            let enemies = Config::load_enemies(&opts.enemies, &weapons).await.unwrap();
             let waves = WavesConfig::load(&opts.waves).await.unwrap();
+            let bosses = BossConfig::load_all(&opts.bosses, &enemies, &waves)
+                .await
+                .unwrap();
             let theme = Theme::load(&opts.theme).await.unwrap();
             StartMenu::new(
                 &geng,
@@ -266,6 +284,8 @@ pub fn run(geng: &Geng, opts: crate::Opts) -> impl geng::State {
                 level,
                 theme,
                 enemies,
+                bosses,
+                weapons,
                 waves,
             )
         }
@@ -1,8 +1,15 @@
 use super::*;
 
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum PlayerAction {
-    Shoot { target_pos: Position },
+    Shoot {
+        target_pos: Position,
+    },
     SwitchState,
     BarrelDash,
+    /// Switch to the weapon that many slots away in the loadout.
+    SwitchWeapon {
+        offset: isize,
+    },
+    Reload,
 }
@@ -0,0 +1,24 @@
+use super::*;
+
+/// The state of the boss fight.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BossState {
+    pub config: BossConfig,
+    pub position: Position,
+    /// Number of phases entered, 0 before the first one.
+    pub phase: usize,
+    /// Time spent in the current phase.
+    pub phase_time: Time,
+    /// Total max health of all parts.
+    pub max_health: Hp,
+    pub minion_cooldown: Time,
+}
+
+impl BossState {
+    /// The config of the current phase, if any has been entered.
+    pub fn current_phase(&self) -> Option<&BossPhaseConfig> {
+        self.phase
+            .checked_sub(1)
+            .and_then(|index| self.config.phases.get(index))
+    }
+}
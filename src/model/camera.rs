@@ -1,8 +1,12 @@
 use super::*;
 
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Camera {
     pub center: Position,
+    /// Center at the start of the last tick, used to interpolate rendering.
+    pub prev_center: Position,
+    /// How far the rendering is between the previous and the current tick, in range `0..=1`.
+    pub interpolation: R32,
     pub offset_center: vec2<Coord>,
     pub fov: Coord,
     pub target_position: Position,
@@ -14,6 +18,8 @@ impl Camera {
     pub fn new(fov: impl Float, world_size: vec2<Coord>) -> Self {
         Self {
             center: Position::zero(world_size),
+            prev_center: Position::zero(world_size),
+            interpolation: R32::ONE,
             offset_center: vec2::ZERO,
             fov: fov.as_r32(),
             target_position: Position::zero(world_size),
@@ -22,9 +28,19 @@ impl Camera {
         }
     }
 
+    /// The center of the camera interpolated between the ticks.
+    pub fn render_center(&self) -> Position {
+        self.interpolate(self.prev_center, self.center)
+    }
+
+    /// Interpolate a position between the ticks.
+    pub fn interpolate(&self, prev: Position, current: Position) -> Position {
+        prev.shifted(prev.delta_to(current) * self.interpolation)
+    }
+
     fn to_camera2d(&self) -> geng::Camera2d {
         geng::Camera2d {
-            center: self.center.to_world().as_f32() + self.offset_center.as_f32(),
+            center: self.render_center().to_world().as_f32() + self.offset_center.as_f32(),
             rotation: Angle::ZERO,
             fov: self.fov.as_f32(),
         }
@@ -32,8 +48,8 @@ impl Camera {
 
     /// Project a world position to a position relative to the camera.
     pub fn project(&self, position: Position) -> vec2<Coord> {
-        let center = self.center.to_world();
-        center + self.center.delta_to(position)
+        let center = self.render_center();
+        center.to_world() + center.delta_to(position)
     }
 
     /// Project a world position to a position relative to the camera.
@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Fraction {
     Player,
     Enemy,
@@ -24,6 +24,21 @@ pub struct VulnerabilityStats {
     pub fire: R32,
     /// Resistance from explosions.
     pub explosive: R32,
+    /// Resistance from poison.
+    pub poison: R32,
+}
+
+impl VulnerabilityStats {
+    /// Damage multiplier for the given damage type.
+    pub fn get(&self, damage_type: DamageType) -> R32 {
+        match damage_type {
+            DamageType::Physical => self.physical,
+            DamageType::Projectile => self.projectile,
+            DamageType::Fire => self.fire,
+            DamageType::Explosive => self.explosive,
+            DamageType::Poison => self.poison,
+        }
+    }
 }
 
 impl Default for VulnerabilityStats {
@@ -33,22 +48,125 @@ impl Default for VulnerabilityStats {
             projectile: R32::ONE,
             fire: R32::ONE,
             explosive: R32::ONE,
+            poison: R32::ONE,
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Controller {
     pub target_velocity: vec2<Coord>,
     pub acceleration: Coord,
 }
 
+/// Weights of the steering behaviours layered on top of the [ActorAI],
+/// so that crowds surround the target instead of stacking.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct SteeringConfig {
+    /// How strongly to keep away from the other actors of the same fraction.
+    pub separation: R32,
+    /// Gap between the bodies below which the separation kicks in.
+    pub separation_radius: Coord,
+    /// How strongly to steer around the blocks ahead.
+    pub avoidance: R32,
+    /// How far ahead to look for the blocks.
+    pub avoidance_distance: Coord,
+    /// How strongly to spread around the target, instead of approaching it from one side.
+    pub flanking: R32,
+    /// Distance to the target below which to start flanking.
+    pub flanking_distance: Coord,
+}
+
+impl Default for SteeringConfig {
+    fn default() -> Self {
+        Self {
+            separation: R32::ZERO,
+            separation_radius: Coord::ZERO,
+            avoidance: R32::ZERO,
+            avoidance_distance: Coord::ZERO,
+            flanking: R32::ZERO,
+            flanking_distance: Coord::ZERO,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum ActorAI {
     Crawler,
-    Ranger { preferred_distance: Coord },
-    BossFoot { position: Position },
-    BossBody,
+    Ranger {
+        preferred_distance: Coord,
+    },
+    /// Telegraphs for a while, then dashes at the target in a straight line.
+    Charger {
+        /// Start charging when the target is that close.
+        charge_distance: Coord,
+        telegraph_time: Time,
+        dash_speed: Coord,
+        dash_time: Time,
+        /// Delay after a dash before the next one.
+        cooldown: Time,
+        #[serde(default)]
+        state: ChargerState,
+    },
+    /// Runs at the target and explodes when close enough.
+    Kamikaze {
+        trigger_distance: Coord,
+        explosion: ExplosionConfig,
+    },
+    /// Circles around the target while shooting.
+    Orbiter {
+        radius: Coord,
+        #[serde(default)]
+        clockwise: bool,
+    },
+    /// Keeps the distance and summons other enemies.
+    Summoner {
+        preferred_distance: Coord,
+        /// Names of the enemies to summon, picked at random.
+        minions: Vec<String>,
+        summon_delay: Time,
+        /// Distance from the summoner at which the minions appear.
+        summon_radius: Coord,
+        /// Do not summon while there are that many enemies alive.
+        max_enemies: usize,
+        #[serde(default)]
+        cooldown: Time,
+    },
+    /// Keeps the distance and aims for a while before shooting,
+    /// so the target can see the shot coming.
+    Sniper {
+        preferred_distance: Coord,
+        aim_time: Time,
+        #[serde(default)]
+        state: SniperState,
+    },
+    BossPart(BossPart),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub enum ChargerState {
+    #[default]
+    Chasing,
+    Telegraphing {
+        time_left: Time,
+        direction: vec2<Coord>,
+    },
+    Dashing {
+        time_left: Time,
+        direction: vec2<Coord>,
+    },
+    Recovering {
+        time_left: Time,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub enum SniperState {
+    #[default]
+    Moving,
+    /// The shot is locked onto the target position.
+    Aiming { time_left: Time, target: Position },
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -62,13 +180,7 @@ pub enum ActorKind {
     BossBody,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct OnFire {
-    pub duration: Time,
-    pub damage_per_second: Hp,
-}
-
-#[derive(SplitFields, Debug)]
+#[derive(SplitFields, Serialize, Deserialize, Debug)]
 #[split(debug, clone)]
 pub struct Actor {
     pub fraction: Fraction,
@@ -80,10 +192,14 @@ pub struct Actor {
     pub stats: Stats,
     pub controller: Controller,
     pub ai: Option<ActorAI>,
+    pub steering: SteeringConfig,
     pub kind: ActorKind,
     pub stops_barrel: bool,
-    pub stunned: Option<Time>,
-    pub on_fire: Option<OnFire>,
+    /// Remaining time of immunity to damage.
+    pub invulnerable: Option<Time>,
+    pub statuses: StatusEffects,
+    /// The last damage taken, used to credit the kill.
+    pub last_hit: Option<DamageSource>,
 }
 
 impl Actor {
@@ -106,10 +222,12 @@ impl Actor {
                 acceleration,
             },
             ai: None,
+            steering: SteeringConfig::default(),
             kind,
             stops_barrel: false,
-            stunned: None,
-            on_fire: None,
+            invulnerable: None,
+            statuses: StatusEffects::default(),
+            last_hit: None,
         }
     }
 
@@ -123,6 +241,7 @@ impl Actor {
             config.kind,
         )
         .with_ai(config.ai)
+        .with_steering(config.steering)
         .stop_barrel(config.stops_barrel);
         if let Some(gun) = config.gun {
             enemy = enemy.with_gun(gun);
@@ -144,6 +263,10 @@ impl Actor {
         }
     }
 
+    pub fn with_steering(self, steering: SteeringConfig) -> Self {
+        Self { steering, ..self }
+    }
+
     pub fn stop_barrel(self, stops_barrel: bool) -> Self {
         Self {
             stops_barrel,
@@ -1,10 +1,12 @@
 use super::*;
 
-#[derive(SplitFields, Debug)]
+#[derive(SplitFields, Serialize, Deserialize, Debug)]
 #[split(debug, clone)]
 pub struct Body {
     #[split(nested)]
     pub collider: Collider,
+    /// Position at the start of the last tick, used to interpolate rendering.
+    pub prev_position: Position,
     pub velocity: vec2<Coord>,
     pub mass: R32,
 }
@@ -13,6 +15,7 @@ impl Body {
     pub fn new(pos: Position, config: BodyConfig) -> Self {
         Self {
             collider: Collider::new(pos, config.shape),
+            prev_position: pos,
             velocity: vec2::ZERO,
             mass: config.mass,
         }
@@ -0,0 +1,76 @@
+use super::*;
+
+/// How a boss part moves relative to its anchor.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub enum BossMotion {
+    /// Stays in place.
+    #[default]
+    Static,
+    /// Swings around the anchor like a pendulum.
+    /// The angle (in radians) is `sin(time * speed) * amplitude + bias`.
+    Swing {
+        /// Offset of the part from the anchor at zero angle.
+        arm: vec2<Coord>,
+        speed: R32,
+        amplitude: R32,
+        #[serde(default)]
+        bias: R32,
+    },
+}
+
+impl BossMotion {
+    /// The position and rotation of the part at the given time.
+    pub fn transform(&self, anchor: Position, time: Time) -> (Position, Angle<Coord>) {
+        match *self {
+            BossMotion::Static => (anchor, Angle::ZERO),
+            BossMotion::Swing {
+                arm,
+                speed,
+                amplitude,
+                bias,
+            } => {
+                let rotation = Angle::from_radians((time * speed).sin() * amplitude + bias);
+                (anchor.shifted(arm.rotate(rotation)), rotation)
+            }
+        }
+    }
+}
+
+/// When a boss part performs its attack.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum AttackTrigger {
+    /// Every that many seconds.
+    Periodic { delay: Time },
+    /// Every tick while the part is rotated further than the angle (in radians).
+    SwingAngle { min_radians: R32 },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BossAttack {
+    pub shot: ShotConfig,
+    pub trigger: AttackTrigger,
+    /// Time until the next periodic attack.
+    #[serde(default)]
+    pub cooldown: Time,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GasolineSpill {
+    pub radius: Coord,
+    /// Amount of gasoline added to the cells it covers.
+    pub amount: R32,
+}
+
+/// A part of a boss, attached to its anchor.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BossPart {
+    pub name: String,
+    pub anchor: Position,
+    pub motion: BossMotion,
+    pub attacks: Vec<BossAttack>,
+    /// Killing the core kills the whole boss.
+    pub core: bool,
+    /// Gasoline spilled when the part dies.
+    pub death_spill: Option<GasolineSpill>,
+    pub death_explosion: Option<ExplosionConfig>,
+}
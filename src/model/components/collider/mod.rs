@@ -57,8 +57,18 @@ impl Collider {
         (self.get_iso(), self.shape.to_parry())
     }
 
+    /// Cheap check of the bounding circles, to avoid constructing the exact shapes.
+    fn bounds_overlap(&self, other: &Self) -> bool {
+        let delta = self.position.delta_to(other.position);
+        delta.len() <= self.shape.bounding_radius() + other.shape.bounding_radius()
+    }
+
     /// Check whether two colliders are intersecting.
     pub fn check(&self, other: &Self) -> bool {
+        if !self.bounds_overlap(other) {
+            return false;
+        }
+
         let delta = self.position.delta_to(other.position).as_f32();
 
         let self_angle = self.rotation.as_radians().as_f32();
@@ -76,6 +86,10 @@ impl Collider {
 
     /// Return the collision info if the two colliders are intersecting.
     pub fn collide(&self, other: &Self) -> Option<Collision> {
+        if !self.bounds_overlap(other) {
+            return None;
+        }
+
         let delta = self.position.delta_to(other.position).as_f32();
 
         let self_angle = self.rotation.as_radians().as_f32();
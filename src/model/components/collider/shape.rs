@@ -7,6 +7,14 @@ pub enum Shape {
 }
 
 impl Shape {
+    /// Radius of the smallest circle around the origin containing the whole shape.
+    pub fn bounding_radius(self) -> Coord {
+        match self {
+            Shape::Circle { radius } => radius,
+            Shape::Rectangle { width, height } => vec2(width, height).len() / r32(2.0),
+        }
+    }
+
     pub fn to_parry(self) -> Box<dyn parry2d::shape::Shape> {
         match self {
             Shape::Circle { radius } => Box::new(parry2d::shape::Ball::new(radius.as_f32())),
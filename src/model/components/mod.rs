@@ -1,40 +1,34 @@
 mod actor;
 mod body;
+mod boss;
 mod collider;
 mod projectile;
+mod status;
 
-pub use self::{actor::*, body::*, collider::*, projectile::*};
+pub use self::{actor::*, body::*, boss::*, collider::*, projectile::*, status::*};
 
 use super::*;
 
-#[derive(SplitFields, Debug)]
-pub struct Gasoline {
-    #[split(nested)]
-    pub collider: Collider,
-    pub lifetime: Lifetime,
-    /// Ignites after being in contact with fire for that time.
-    pub ignite_timer: Time,
-    pub fire_radius: Coord,
-    pub explosion: ExplosionConfig,
-    pub fire: FireConfig,
-}
-
-#[derive(SplitFields, Debug)]
+#[derive(SplitFields, Serialize, Deserialize, Debug)]
+#[split(debug, clone)]
 pub struct Fire {
     #[split(nested)]
     pub collider: Collider,
     pub lifetime: Lifetime,
     pub config: FireConfig,
+    pub source: DamageSource,
 }
 
-#[derive(SplitFields, Debug, Clone)]
+#[derive(SplitFields, Serialize, Deserialize, Debug, Clone)]
+#[split(debug, clone)]
 pub struct Explosion {
     pub position: Position,
     pub max_radius: Coord,
     pub lifetime: Lifetime,
 }
 
-#[derive(SplitFields, Debug)]
+#[derive(SplitFields, Serialize, Deserialize, Debug)]
+#[split(debug, clone)]
 pub struct Particle {
     pub position: Position,
     pub size: Coord,
@@ -43,24 +37,33 @@ pub struct Particle {
     pub kind: ParticleKind,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
 pub enum ParticleKind {
     Fire,
     Damage,
     Heal,
     Projectile,
+    Poison,
+    Frost,
+    Oil,
+    Debris,
 }
 
-#[derive(SplitFields, Debug)]
+#[derive(SplitFields, Serialize, Deserialize, Debug)]
+#[split(debug, clone)]
 pub struct Block {
     #[split(nested)]
     pub collider: Collider,
     pub health: Option<Health>,
-    pub on_fire: Option<OnFire>,
+    pub statuses: StatusEffects,
+    /// How quickly the block catches fire from the burning neighbours.
+    pub flammability: R32,
     pub vulnerability: VulnerabilityStats,
     pub color: Color,
     pub kind: BlockKind,
     pub explosion: Option<ExplosionConfig>,
+    /// The last damage taken, used to credit the explosion.
+    pub last_hit: Option<DamageSource>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy)]
@@ -69,7 +72,8 @@ pub enum BlockKind {
     Barrel,
 }
 
-#[derive(SplitFields, Debug)]
+#[derive(SplitFields, Serialize, Deserialize, Debug)]
+#[split(debug, clone)]
 pub struct PickUp {
     #[split(nested)]
     pub body: Body,
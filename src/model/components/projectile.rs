@@ -6,12 +6,33 @@ pub enum ProjectileAI {
     ConstantTurn {
         degrees_per_second: R32,
     },
+    /// Turns toward the nearest hostile actor within the cone in front of it.
+    Homing {
+        degrees_per_second: R32,
+        /// Full angle of the cone.
+        cone_degrees: R32,
+        range: Coord,
+    },
+    /// Flies straight, then turns back and returns to the shooter.
+    Boomerang {
+        return_after: Time,
+        degrees_per_second: R32,
+    },
     CircleBomb {
         explosive_type: Box<ProjectileConfig>,
         delay: Time,
+        /// Fired when the bomb explodes.
+        #[serde(default = "ProjectileAI::default_bomb_pattern")]
+        pattern: ShotPattern,
     },
 }
 
+impl ProjectileAI {
+    fn default_bomb_pattern() -> ShotPattern {
+        ShotPattern::Ring { bullets: 18 }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum ProjectileKind {
     Default,
@@ -20,12 +41,18 @@ pub enum ProjectileKind {
     SquareSnowflake,
     SquidLike,
     WheelPizza,
+    /// A barrel launched by ramming into it.
+    Barrel,
 }
 
-#[derive(SplitFields, Debug)]
+#[derive(SplitFields, Serialize, Deserialize, Debug)]
+#[split(debug, clone)]
 pub struct Projectile {
     pub lifetime: Lifetime,
     pub fraction: Fraction,
+    /// The actor that fired the projectile.
+    #[serde(with = "snapshot_id::option")]
+    pub owner: Option<Id>,
     #[split(nested)]
     pub body: Body,
     pub damage: Hp,
@@ -33,6 +60,39 @@ pub struct Projectile {
     pub ai: ProjectileAI,
     pub kind: ProjectileKind,
     pub knockback: Coord,
+    /// Status effects applied to the target on hit.
+    pub statuses: Vec<StatusEffect>,
+    /// Bounces off the blocks left.
+    pub bounces: usize,
+    /// Actors left to pass through.
+    pub pierce: usize,
+    /// Actors already passed through, so that they are not hit again.
+    #[serde(with = "snapshot_id::vec")]
+    pub pierced: Vec<Id>,
+    /// Speed before applying the speed curve.
+    pub speed: Coord,
+    pub speed_curve: Vec<R32>,
+    pub thrown: Option<Thrown>,
+}
+
+/// A projectile thrown in an arc, landing at its `target_pos`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Thrown {
+    pub config: ThrowConfig,
+    /// Distance from the launch point to the target.
+    pub distance: Coord,
+}
+
+impl Thrown {
+    /// Height above the ground at the position on the way to the target.
+    pub fn height(&self, position: Position, target: Position) -> Coord {
+        if self.distance <= Coord::ZERO {
+            return Coord::ZERO;
+        }
+        let remaining = position.distance(target);
+        let t = (R32::ONE - remaining / self.distance).clamp(R32::ZERO, R32::ONE);
+        self.config.arc_height * r32(4.0) * t * (R32::ONE - t)
+    }
 }
 
 impl Projectile {
@@ -44,6 +104,7 @@ impl Projectile {
     ) -> Self {
         Self {
             fraction,
+            owner: None,
             body: Body::new(pos, config.body).with_velocity(direction.unit_vec() * config.speed),
             lifetime: Lifetime::new_max(config.lifetime),
             damage: config.damage,
@@ -51,16 +112,40 @@ impl Projectile {
             ai: config.ai,
             kind: config.kind,
             knockback: config.knockback,
+            statuses: config.statuses,
+            bounces: config.bounces,
+            pierce: config.pierce,
+            pierced: Vec::new(),
+            speed: config.speed,
+            speed_curve: config.speed_curve,
+            thrown: config.throw.map(|config| Thrown {
+                config,
+                distance: Coord::ZERO,
+            }),
         }
     }
 
-    // TODO: grenades or smth
-    // pub fn with_target(self, target_pos: vec2<Coord>) -> Self {
-    //     Self {
-    //         target_pos: Some(target_pos),
-    //         ..self
-    //     }
-    // }
+    pub fn with_owner(self, owner: Option<Id>) -> Self {
+        Self { owner, ..self }
+    }
+
+    /// The source of the damage dealt by the projectile.
+    pub fn damage_source(&self) -> DamageSource {
+        DamageSource {
+            owner: self.owner,
+            fraction: Some(self.fraction),
+            mechanic: DamageMechanic::Projectile,
+        }
+    }
+
+    pub fn with_target(self, target_pos: Position) -> Self {
+        let distance = self.body.collider.position.distance(target_pos);
+        Self {
+            target_pos: Some(target_pos),
+            thrown: self.thrown.map(|thrown| Thrown { distance, ..thrown }),
+            ..self
+        }
+    }
 }
 
 impl Default for ProjectileAI {
@@ -0,0 +1,324 @@
+use super::*;
+
+/// A temporary effect on an actor or a block.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub duration: Time,
+    /// How the effect combines with an active effect of the same kind.
+    #[serde(default)]
+    pub stacking: Stacking,
+    /// Who applied the effect, credited for the damage it deals.
+    #[serde(default)]
+    pub source: Option<DamageSource>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum StatusKind {
+    /// Takes fire damage over time.
+    Burning {
+        damage_per_second: Hp,
+        /// Once the fire goes out, the entity does not catch fire again for that time.
+        #[serde(default)]
+        burnt_out: Time,
+    },
+    /// Cannot move or act.
+    Stunned,
+    /// Moves slower.
+    Slowed { speed_multiplier: R32 },
+    /// Soaked in oil, takes more fire damage.
+    OilSoaked { fire_multiplier: R32 },
+    /// Takes poison damage over time.
+    Poisoned { damage_per_second: Hp },
+    /// Absorbs that much damage before it gets through.
+    Shielded { amount: Hp },
+    /// Cannot move or act. Freezing puts out the fire, and catching fire thaws.
+    Frozen,
+    /// Recently burnt, does not catch fire.
+    BurntOut,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+pub enum Stacking {
+    /// Keep the longest duration and the strongest effect.
+    #[default]
+    Refresh,
+    /// Add up the durations, keep the strongest effect.
+    Extend,
+    /// Add up the strength of the effects, keep the longest duration.
+    Intensify,
+    /// Replace the active effect.
+    Replace,
+}
+
+/// All status effects active on an actor or a block, at most one of each kind.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct StatusEffects {
+    effects: Vec<StatusEffect>,
+    /// Progress towards catching fire from the burning neighbours, ignites at 1.
+    pub heat: R32,
+}
+
+impl Default for StatusEffects {
+    fn default() -> Self {
+        Self {
+            effects: Vec::new(),
+            heat: R32::ZERO,
+        }
+    }
+}
+
+impl StatusKind {
+    /// Whether the two effects are of the same kind, ignoring the strength.
+    pub fn same_kind(self, other: Self) -> bool {
+        std::mem::discriminant(&self) == std::mem::discriminant(&other)
+    }
+
+    /// Damage dealt every second and its type.
+    pub fn damage_per_second(self) -> Option<(Hp, DamageType)> {
+        match self {
+            StatusKind::Burning {
+                damage_per_second, ..
+            } => Some((damage_per_second, DamageType::Fire)),
+            StatusKind::Poisoned { damage_per_second } => {
+                Some((damage_per_second, DamageType::Poison))
+            }
+            _ => None,
+        }
+    }
+
+    /// How the damage dealt by the effect is credited.
+    pub fn mechanic(self) -> DamageMechanic {
+        match self {
+            StatusKind::Poisoned { .. } => DamageMechanic::Poison,
+            _ => DamageMechanic::Fire,
+        }
+    }
+
+    /// The particles emitted by the affected entity, and their intensity.
+    pub fn particles(self) -> Option<(ParticleKind, R32)> {
+        match self {
+            StatusKind::Burning {
+                damage_per_second, ..
+            } => Some((ParticleKind::Fire, damage_per_second)),
+            StatusKind::Poisoned { .. } => Some((ParticleKind::Poison, r32(0.3))),
+            StatusKind::Frozen => Some((ParticleKind::Frost, r32(0.3))),
+            StatusKind::OilSoaked { .. } => Some((ParticleKind::Oil, r32(0.2))),
+            StatusKind::Stunned
+            | StatusKind::Slowed { .. }
+            | StatusKind::Shielded { .. }
+            | StatusKind::BurntOut => None,
+        }
+    }
+
+    /// Combine the strength of the effects, keeping the kind.
+    fn merge(&mut self, other: Self, add: bool) {
+        let combine = |a: &mut R32, b: R32| {
+            *a = if add { *a + b } else { (*a).max(b) };
+        };
+        match (self, other) {
+            (
+                StatusKind::Burning {
+                    damage_per_second,
+                    burnt_out,
+                },
+                StatusKind::Burning {
+                    damage_per_second: other,
+                    burnt_out: other_burnt_out,
+                },
+            ) => {
+                combine(damage_per_second, other);
+                *burnt_out = (*burnt_out).max(other_burnt_out);
+            }
+            (
+                StatusKind::Poisoned { damage_per_second },
+                StatusKind::Poisoned {
+                    damage_per_second: other,
+                },
+            ) => combine(damage_per_second, other),
+            (
+                StatusKind::OilSoaked { fire_multiplier },
+                StatusKind::OilSoaked {
+                    fire_multiplier: other,
+                },
+            ) => combine(fire_multiplier, other),
+            (StatusKind::Shielded { amount }, StatusKind::Shielded { amount: other }) => {
+                combine(amount, other);
+            }
+            (
+                StatusKind::Slowed { speed_multiplier },
+                StatusKind::Slowed {
+                    speed_multiplier: other,
+                },
+            ) => {
+                // The stronger slow is the lower multiplier
+                *speed_multiplier = if add {
+                    *speed_multiplier * other
+                } else {
+                    (*speed_multiplier).min(other)
+                };
+            }
+            _ => {}
+        }
+    }
+}
+
+impl StatusEffects {
+    pub fn iter(&self) -> impl Iterator<Item = &StatusEffect> {
+        self.effects.iter()
+    }
+
+    /// See [DamageSource::remap_owner].
+    pub fn remap_owners(&mut self, ids: &HashMap<Id, Id>) {
+        for effect in &mut self.effects {
+            effect.source = effect.source.map(|source| source.remap_owner(ids));
+        }
+    }
+
+    /// The fire burning the entity, if any.
+    pub fn burning(&self) -> Option<&StatusEffect> {
+        self.effects
+            .iter()
+            .find(|effect| matches!(effect.kind, StatusKind::Burning { .. }))
+    }
+
+    /// The oil soaking the entity, if any.
+    pub fn soaked(&self) -> Option<&StatusEffect> {
+        self.effects
+            .iter()
+            .find(|effect| matches!(effect.kind, StatusKind::OilSoaked { .. }))
+    }
+
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.effects
+            .iter()
+            .any(|effect| effect.kind.same_kind(kind))
+    }
+
+    pub fn remove(&mut self, kind: StatusKind) {
+        self.effects.retain(|effect| !effect.kind.same_kind(kind));
+    }
+
+    /// Apply the effect, combining it with the active one of the same kind.
+    pub fn apply(&mut self, effect: StatusEffect) {
+        match effect.kind {
+            StatusKind::Burning { .. } => {
+                if self.has(StatusKind::BurntOut) {
+                    return;
+                }
+                // Fire and ice cancel each other out
+                self.remove(StatusKind::Frozen);
+            }
+            StatusKind::Frozen => self.remove(StatusKind::Burning {
+                damage_per_second: Hp::ZERO,
+                burnt_out: Time::ZERO,
+            }),
+            _ => {}
+        }
+
+        let Some(active) = self
+            .effects
+            .iter_mut()
+            .find(|active| active.kind.same_kind(effect.kind))
+        else {
+            self.effects.push(effect);
+            return;
+        };
+
+        match effect.stacking {
+            Stacking::Refresh => {
+                active.duration = active.duration.max(effect.duration);
+                active.kind.merge(effect.kind, false);
+            }
+            Stacking::Extend => {
+                active.duration += effect.duration;
+                active.kind.merge(effect.kind, false);
+            }
+            Stacking::Intensify => {
+                active.duration = active.duration.max(effect.duration);
+                active.kind.merge(effect.kind, true);
+            }
+            Stacking::Replace => {
+                *active = effect;
+                return;
+            }
+        }
+        active.source = effect.source.or(active.source);
+    }
+
+    /// Count down the durations and remove the expired effects.
+    pub fn update(&mut self, delta_time: Time) {
+        for effect in &mut self.effects {
+            effect.duration -= delta_time;
+        }
+        let burnt_out = self.effects.iter().find_map(|effect| match effect.kind {
+            StatusKind::Burning { burnt_out, .. }
+                if effect.duration <= Time::ZERO && burnt_out > Time::ZERO =>
+            {
+                Some(burnt_out)
+            }
+            _ => None,
+        });
+        self.effects.retain(|effect| effect.duration > Time::ZERO);
+
+        if let Some(duration) = burnt_out {
+            self.effects.push(StatusEffect {
+                kind: StatusKind::BurntOut,
+                duration,
+                stacking: Stacking::Refresh,
+                source: None,
+            });
+        }
+    }
+
+    /// Whether the entity is unable to move or act.
+    pub fn is_disabled(&self) -> bool {
+        self.effects
+            .iter()
+            .any(|effect| matches!(effect.kind, StatusKind::Stunned | StatusKind::Frozen))
+    }
+
+    pub fn speed_multiplier(&self) -> R32 {
+        self.effects
+            .iter()
+            .filter_map(|effect| match effect.kind {
+                StatusKind::Slowed { speed_multiplier } => Some(speed_multiplier),
+                _ => None,
+            })
+            .fold(R32::ONE, |acc, mult| acc * mult)
+    }
+
+    /// Damage multiplier from the effects.
+    pub fn vulnerability(&self, damage_type: DamageType) -> R32 {
+        self.effects
+            .iter()
+            .filter_map(|effect| match (effect.kind, damage_type) {
+                (StatusKind::OilSoaked { fire_multiplier }, DamageType::Fire) => {
+                    Some(fire_multiplier)
+                }
+                _ => None,
+            })
+            .fold(R32::ONE, |acc, mult| acc * mult)
+    }
+
+    /// Absorb the damage by the shield, returns the damage that got through.
+    pub fn absorb(&mut self, damage: Hp) -> Hp {
+        let Some(index) = self
+            .effects
+            .iter()
+            .position(|effect| matches!(effect.kind, StatusKind::Shielded { .. }))
+        else {
+            return damage;
+        };
+        let StatusKind::Shielded { amount } = &mut self.effects[index].kind else {
+            unreachable!()
+        };
+        let absorbed = damage.min(*amount);
+        *amount -= absorbed;
+        if *amount <= Hp::ZERO {
+            self.effects.remove(index);
+        }
+        damage - absorbed
+    }
+}
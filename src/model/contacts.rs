@@ -0,0 +1,217 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContactPhase {
+    /// The pair started touching this tick.
+    Enter,
+    /// The pair was already touching during the previous tick.
+    Stay,
+}
+
+/// Tracks pairs of touching entities between ticks,
+/// so that the effects of a contact (like damage) are applied only once.
+#[derive(Debug, Clone, Default)]
+pub struct Contacts {
+    pairs: HashMap<(Id, Id), Contact>,
+    /// Order of the next contact to start.
+    next_order: u64,
+}
+
+/// [Contacts] with the ids replaced by their indices in a [ModelSnapshot],
+/// since arena ids are not preserved between snapshots.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ContactsSnapshot {
+    pairs: Vec<((usize, usize), Contact)>,
+    next_order: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+struct Contact {
+    /// When the contact started relative to the others,
+    /// since the iteration order of a `HashMap` is random.
+    order: u64,
+    touched: bool,
+    hit: ContactHit,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+enum ContactHit {
+    /// The effect can be applied.
+    Ready,
+    /// The effect can be applied again after the cooldown.
+    Cooldown(Time),
+    /// The effect has been applied and cannot be applied again during this contact.
+    Done,
+}
+
+impl Contacts {
+    /// Register that the pair is touching during this tick.
+    pub fn touch(&mut self, pair: (Id, Id)) -> ContactPhase {
+        match self.pairs.entry(pair) {
+            std::collections::hash_map::Entry::Occupied(mut entry) => {
+                entry.get_mut().touched = true;
+                ContactPhase::Stay
+            }
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(Contact {
+                    order: self.next_order,
+                    touched: true,
+                    hit: ContactHit::Ready,
+                });
+                self.next_order += 1;
+                ContactPhase::Enter
+            }
+        }
+    }
+
+    /// Whether the effect of the contact can be applied.
+    pub fn is_ready(&self, pair: (Id, Id)) -> bool {
+        self.pairs
+            .get(&pair)
+            .is_none_or(|contact| matches!(contact.hit, ContactHit::Ready))
+    }
+
+    /// Mark the effect of the contact as applied.
+    /// With a cooldown, the effect becomes ready again after that time, if the pair is still touching.
+    /// Otherwise, it is applied only once per contact.
+    pub fn hit(&mut self, pair: (Id, Id), cooldown: Option<Time>) {
+        if let Some(contact) = self.pairs.get_mut(&pair) {
+            contact.hit = match cooldown {
+                Some(time) => ContactHit::Cooldown(time),
+                None => ContactHit::Done,
+            };
+        }
+    }
+
+    /// Update the cooldowns and forget the pairs that were not touching during this tick.
+    /// Returns the pairs that stopped touching, in the order the contacts started.
+    pub fn update(&mut self, delta_time: Time) -> Vec<(Id, Id)> {
+        let mut exited = Vec::new();
+        self.pairs.retain(|&pair, contact| {
+            if !contact.touched {
+                exited.push((contact.order, pair));
+                return false;
+            }
+            contact.touched = false;
+            if let ContactHit::Cooldown(time) = &mut contact.hit {
+                *time -= delta_time;
+                if *time <= Time::ZERO {
+                    contact.hit = ContactHit::Ready;
+                }
+            }
+            true
+        });
+        exited.sort_by_key(|&(order, _)| order);
+        exited.into_iter().map(|(_, pair)| pair).collect()
+    }
+
+    /// Replace the ids in the pairs with the indices of the entities in the snapshot.
+    /// The pairs with the entities missing from the snapshot are dropped.
+    pub fn to_snapshot(
+        &self,
+        first: &HashMap<Id, usize>,
+        second: &HashMap<Id, usize>,
+    ) -> ContactsSnapshot {
+        let mut pairs: Vec<((usize, usize), Contact)> = self
+            .pairs
+            .iter()
+            .filter_map(|((a, b), &contact)| Some(((*first.get(a)?, *second.get(b)?), contact)))
+            .collect();
+        // Keep the snapshots deterministic
+        pairs.sort_by_key(|&(pair, _)| pair);
+        ContactsSnapshot {
+            pairs,
+            next_order: self.next_order,
+        }
+    }
+
+    /// Restore the pairs from the snapshot, given the new ids of the entities in the index order.
+    pub fn from_snapshot(snapshot: ContactsSnapshot, first: &[Id], second: &[Id]) -> Self {
+        Self {
+            pairs: snapshot
+                .pairs
+                .into_iter()
+                .filter_map(|((a, b), contact)| Some(((*first.get(a)?, *second.get(b)?), contact)))
+                .collect(),
+            next_order: snapshot.next_order,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(count: usize) -> Vec<Id> {
+        let mut arena = Arena::<()>::default();
+        (0..count).map(|_| arena.insert(())).collect()
+    }
+
+    #[test]
+    fn enter_stay_exit() {
+        let ids = ids(2);
+        let pair = (ids[0], ids[1]);
+        let mut contacts = Contacts::default();
+
+        assert_eq!(contacts.touch(pair), ContactPhase::Enter);
+        assert!(contacts.update(r32(0.1)).is_empty());
+        assert_eq!(contacts.touch(pair), ContactPhase::Stay);
+        assert!(contacts.update(r32(0.1)).is_empty());
+
+        // Not touching during a tick
+        assert_eq!(contacts.update(r32(0.1)), vec![pair]);
+        assert!(contacts.update(r32(0.1)).is_empty());
+        assert_eq!(contacts.touch(pair), ContactPhase::Enter);
+    }
+
+    #[test]
+    fn hit_once_per_contact() {
+        let ids = ids(2);
+        let pair = (ids[0], ids[1]);
+        let mut contacts = Contacts::default();
+
+        contacts.touch(pair);
+        assert!(contacts.is_ready(pair));
+        contacts.hit(pair, None);
+        assert!(!contacts.is_ready(pair));
+        contacts.update(r32(10.0));
+        contacts.touch(pair);
+        assert!(!contacts.is_ready(pair));
+
+        // Ready again after the contact is lost
+        contacts.update(r32(0.1));
+        contacts.update(r32(0.1));
+        contacts.touch(pair);
+        assert!(contacts.is_ready(pair));
+    }
+
+    #[test]
+    fn hit_cooldown() {
+        let ids = ids(2);
+        let pair = (ids[0], ids[1]);
+        let mut contacts = Contacts::default();
+
+        contacts.touch(pair);
+        contacts.hit(pair, Some(r32(0.25)));
+        contacts.update(r32(0.1));
+        contacts.touch(pair);
+        assert!(!contacts.is_ready(pair));
+        contacts.update(r32(0.2));
+        contacts.touch(pair);
+        assert!(contacts.is_ready(pair));
+    }
+
+    #[test]
+    fn exits_in_start_order() {
+        let ids = ids(8);
+        let pairs: Vec<(Id, Id)> = (1..ids.len()).map(|i| (ids[0], ids[i])).collect();
+        let mut contacts = Contacts::default();
+        for &pair in pairs.iter().rev() {
+            contacts.touch(pair);
+        }
+        contacts.update(r32(0.1));
+
+        let expected: Vec<(Id, Id)> = pairs.iter().rev().copied().collect();
+        assert_eq!(contacts.update(r32(0.1)), expected);
+    }
+}
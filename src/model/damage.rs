@@ -0,0 +1,73 @@
+use super::*;
+
+/// Who and what dealt the damage.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DamageSource {
+    /// The actor responsible for the damage, if any.
+    #[serde(with = "snapshot_id::option")]
+    pub owner: Option<Id>,
+    /// The fraction responsible for the damage, if known.
+    pub fraction: Option<Fraction>,
+    pub mechanic: DamageMechanic,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageType {
+    Physical,
+    Projectile,
+    Fire,
+    Explosive,
+    Poison,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DamageTarget {
+    Actor(Id),
+    Block(Id),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageMechanic {
+    /// Bumping into an actor.
+    Contact,
+    /// Getting run over by the barrel.
+    Runover,
+    Projectile,
+    Fire,
+    Explosion,
+    Poison,
+}
+
+impl DamageSource {
+    /// Damage caused by an actor.
+    pub fn actor(owner: Id, fraction: Fraction, mechanic: DamageMechanic) -> Self {
+        Self {
+            owner: Some(owner),
+            fraction: Some(fraction),
+            mechanic,
+        }
+    }
+
+    /// Damage caused by the level itself.
+    pub fn environment(mechanic: DamageMechanic) -> Self {
+        Self {
+            owner: None,
+            fraction: None,
+            mechanic,
+        }
+    }
+
+    /// The same culprit, dealing damage in a different way.
+    /// Used to credit chain reactions, like an explosion igniting gasoline.
+    pub fn with_mechanic(self, mechanic: DamageMechanic) -> Self {
+        Self { mechanic, ..self }
+    }
+
+    /// Point to the owner by its id in the restored snapshot, forgetting it if it is gone.
+    pub fn remap_owner(self, ids: &HashMap<Id, Id>) -> Self {
+        Self {
+            owner: self.owner.and_then(|owner| ids.get(&owner).copied()),
+            ..self
+        }
+    }
+}
@@ -1,11 +1,11 @@
 use super::*;
 
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct QueuedEffect {
     pub effect: Effect,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum Effect {
     // /// No operation.
     // Noop,
@@ -13,6 +13,7 @@ pub enum Effect {
     Explosion {
         position: Position,
         config: ExplosionConfig,
+        source: DamageSource,
     },
     Particles {
         position: Position,
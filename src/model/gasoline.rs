@@ -0,0 +1,379 @@
+use super::*;
+
+/// Gasoline on the ground, stored as a density grid wrapping around the torus.
+/// The gasoline pools, slowly spreads to the neighbouring cells and evaporates,
+/// and the fire travels through it cell by cell.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GasolineField {
+    config: GasolineFieldConfig,
+    world_size: vec2<Coord>,
+    /// Number of cells along each axis.
+    grid_size: vec2<i64>,
+    cell_size: vec2<Coord>,
+    /// Amount of gasoline in every cell.
+    density: Vec<R32>,
+    /// How long every cell has been burning, `None` if it is not on fire.
+    burning: Vec<Option<Time>>,
+    /// Who spilled the gasoline in every cell, `None` for the empty cells.
+    source: Vec<Option<DamageSource>>,
+    /// Whether the gasoline in every cell soaks the actors walking through,
+    /// `false` for the cells with only the drops of the soaked actors.
+    soaks: Vec<bool>,
+    /// Cells with gasoline or fire in them, the only ones that need updating.
+    active: Vec<usize>,
+    /// Whether every cell is in the `active` list.
+    is_active: Vec<bool>,
+    /// Cells that caught fire since the last update.
+    ignited: Vec<usize>,
+}
+
+/// A cell that has just caught fire.
+#[derive(Debug, Clone, Copy)]
+pub struct IgnitedCell {
+    pub position: Position,
+    /// Whether the cell is one of the sparse cells that explode when ignited.
+    pub explodes: bool,
+    pub source: DamageSource,
+}
+
+/// A cell with gasoline, for rendering.
+#[derive(Debug, Clone, Copy)]
+pub struct GasolineCell {
+    pub position: Position,
+    pub density: R32,
+    /// How long the cell has been burning.
+    pub burning: Option<Time>,
+}
+
+impl GasolineField {
+    /// The cell size gets adjusted so that the cells tile the world exactly.
+    pub fn new(world_size: vec2<Coord>, config: GasolineFieldConfig) -> Self {
+        let grid_size =
+            world_size.map(|size| (size / config.cell_size).as_f32().floor().max(1.0) as i64);
+        let cells = usize::try_from(grid_size.x * grid_size.y).unwrap();
+        Self {
+            config,
+            world_size,
+            grid_size,
+            cell_size: vec2(
+                world_size.x / r32(grid_size.x as f32),
+                world_size.y / r32(grid_size.y as f32),
+            ),
+            density: vec![R32::ZERO; cells],
+            burning: vec![None; cells],
+            source: vec![None; cells],
+            soaks: vec![false; cells],
+            active: Vec::new(),
+            is_active: vec![false; cells],
+            ignited: Vec::new(),
+        }
+    }
+
+    pub fn cell_size(&self) -> vec2<Coord> {
+        self.cell_size
+    }
+
+    /// All the cells with gasoline in them.
+    pub fn cells(&self) -> impl Iterator<Item = GasolineCell> + '_ {
+        self.active
+            .iter()
+            .filter(|&&index| self.density[index] > R32::ZERO)
+            .map(|&index| GasolineCell {
+                position: self.cell_center(self.cell(index)),
+                density: self.density[index],
+                burning: self.burning[index],
+            })
+    }
+
+    pub fn density(&self, position: Position) -> R32 {
+        self.density[self.index(self.to_cell(position))]
+    }
+
+    /// See [DamageSource::remap_owner].
+    pub fn remap_owners(&mut self, ids: &HashMap<Id, Id>) {
+        for source in self.source.iter_mut().flatten() {
+            *source = source.remap_owner(ids);
+        }
+    }
+
+    /// Who spilled the gasoline at the position.
+    pub fn source(&self, position: Position) -> Option<DamageSource> {
+        self.source[self.index(self.to_cell(position))]
+    }
+
+    /// Whether the gasoline at the position soaks the actors walking through.
+    pub fn soaks(&self, position: Position) -> bool {
+        self.soaks[self.index(self.to_cell(position))]
+    }
+
+    /// Who spilled the burning gasoline within the radius, if any is on fire.
+    pub fn burning_near(&self, position: Position, radius: Coord) -> Option<DamageSource> {
+        self.cells_near(position, radius)
+            .into_iter()
+            .find(|&index| self.burning[index].is_some())
+            .and_then(|index| self.source[index])
+    }
+
+    /// Spill the gasoline over the cells within the radius.
+    pub fn spill(&mut self, position: Position, radius: Coord, amount: R32, source: DamageSource) {
+        self.pour(position, radius, amount, source, true);
+    }
+
+    /// Drip the gasoline over the cells within the radius.
+    /// Unlike [Self::spill], the drops alone do not soak the actors,
+    /// otherwise the soaked actors would keep soaking themselves in their own drops.
+    pub fn drip(&mut self, position: Position, radius: Coord, amount: R32, source: DamageSource) {
+        self.pour(position, radius, amount, source, false);
+    }
+
+    fn pour(
+        &mut self,
+        position: Position,
+        radius: Coord,
+        amount: R32,
+        source: DamageSource,
+        soaks: bool,
+    ) {
+        for index in self.cells_near(position, radius) {
+            self.density[index] += amount;
+            self.soaks[index] |= soaks;
+            self.activate(index);
+            let known = self.source[index].is_some_and(|source| source.fraction.is_some());
+            if !known {
+                self.source[index] = Some(source);
+            }
+        }
+    }
+
+    /// Set the gasoline within the radius on fire.
+    /// The fire is credited to whoever spilled the gasoline, or the `igniter` if that is unknown.
+    /// Returns whether anything caught fire.
+    pub fn ignite(&mut self, position: Position, radius: Coord, igniter: DamageSource) -> bool {
+        let mut ignited = false;
+        for index in self.cells_near(position, radius) {
+            ignited |= self.ignite_cell(index, igniter);
+        }
+        ignited
+    }
+
+    /// Spread, drift along the `flow`, evaporate and burn the gasoline.
+    /// The fire spreads faster downwind, according to the `forces` response.
+    /// Returns the cells that caught fire since the last update.
+    pub fn update(
+        &mut self,
+        delta_time: Time,
+        fire: &FireConfig,
+        forces: &ForceResponseConfig,
+        flow: impl Fn(Position) -> vec2<Coord>,
+    ) -> Vec<IgnitedCell> {
+        self.spread(delta_time);
+        self.drift(delta_time, |position| flow(position) * forces.gasoline);
+
+        let mut spreading: Vec<(usize, DamageSource)> = Vec::new();
+        let mut active = std::mem::take(&mut self.active);
+        for &index in &active {
+            let cell = self.cell(index);
+            let wind = if self.burning[index].is_some() {
+                flow(self.cell_center(cell))
+            } else {
+                vec2::ZERO
+            };
+            if let Some(time) = &mut self.burning[index] {
+                let prev_time = *time;
+                *time += delta_time;
+                if let Some(source) = self.source[index] {
+                    // Every neighbour catches fire after its own delay
+                    for delta in [vec2(1, 0), vec2(-1, 0), vec2(0, 1), vec2(0, -1)] {
+                        let dir = delta.map(|x| r32(x as f32));
+                        let bias = R32::ONE + vec2::dot(wind, dir) * forces.fire;
+                        if bias <= R32::ZERO {
+                            continue;
+                        }
+                        let spread_time = fire.spread_time / bias;
+                        if prev_time < spread_time && *time >= spread_time {
+                            spreading.push((self.index(cell + delta), source));
+                        }
+                    }
+                }
+                if *time >= fire.lifetime {
+                    // Burnt out
+                    self.burning[index] = None;
+                    self.density[index] = R32::ZERO;
+                }
+            } else {
+                self.density[index] =
+                    (self.density[index] - self.config.evaporation * delta_time).max(R32::ZERO);
+            }
+            if self.density[index] <= R32::ZERO {
+                self.source[index] = None;
+                self.soaks[index] = false;
+            }
+        }
+        active.retain(|&index| {
+            let keep = self.density[index] > R32::ZERO || self.burning[index].is_some();
+            self.is_active[index] = keep;
+            keep
+        });
+        self.active = active;
+
+        // The burning front
+        for (index, source) in spreading {
+            self.ignite_cell(index, source);
+        }
+
+        let spacing = (self.config.explosion_spacing / self.cell_size.x)
+            .as_f32()
+            .round()
+            .max(1.0) as i64;
+        std::mem::take(&mut self.ignited)
+            .into_iter()
+            .filter_map(|index| {
+                let cell = self.cell(index);
+                Some(IgnitedCell {
+                    position: self.cell_center(cell),
+                    explodes: cell.x % spacing == 0 && cell.y % spacing == 0,
+                    source: self.source[index]?,
+                })
+            })
+            .collect()
+    }
+
+    /// The gasoline above the pool depth flows out evenly to the neighbouring cells.
+    fn spread(&mut self, delta_time: Time) {
+        let rate = (self.config.spread_rate * delta_time).min(R32::ONE);
+        let outflow: Vec<(usize, R32)> = self
+            .active
+            .iter()
+            .map(|&index| {
+                let flow = (self.density[index] - self.config.pool_depth).max(R32::ZERO) * rate;
+                (index, flow)
+            })
+            .filter(|&(_, flow)| flow > R32::ZERO)
+            .collect();
+
+        for (index, flow) in outflow {
+            self.density[index] -= flow;
+            let cell = self.cell(index);
+            for delta in [vec2(1, 0), vec2(-1, 0), vec2(0, 1), vec2(0, -1)] {
+                let next = self.index(cell + delta);
+                self.density[next] += flow / r32(4.0);
+                self.soaks[next] |= self.soaks[index];
+                self.activate(next);
+                if self.source[next].is_none() {
+                    self.source[next] = self.source[index];
+                }
+            }
+        }
+    }
+
+    /// The gasoline flows to the neighbouring cells downstream.
+    fn drift(&mut self, delta_time: Time, flow: impl Fn(Position) -> vec2<Coord>) {
+        let mut moved: Vec<(usize, usize, R32)> = Vec::new();
+        for &index in &self.active {
+            let density = self.density[index];
+            if density <= R32::ZERO {
+                continue;
+            }
+            let cell = self.cell(index);
+            let velocity = flow(self.cell_center(cell));
+            // Fraction of the cell crossing over along each axis
+            let fraction = vec2(
+                (velocity.x.abs() * delta_time / self.cell_size.x).min(r32(0.5)),
+                (velocity.y.abs() * delta_time / self.cell_size.y).min(r32(0.5)),
+            );
+            let step = velocity.map(|v| if v > Coord::ZERO { 1 } else { -1 });
+            if fraction.x > R32::ZERO {
+                let next = self.index(cell + vec2(step.x, 0));
+                moved.push((index, next, density * fraction.x));
+            }
+            if fraction.y > R32::ZERO {
+                let next = self.index(cell + vec2(0, step.y));
+                moved.push((index, next, density * fraction.y));
+            }
+        }
+
+        for (from, to, amount) in moved {
+            self.density[from] -= amount;
+            self.density[to] += amount;
+            self.soaks[to] |= self.soaks[from];
+            self.activate(to);
+            if self.source[to].is_none() {
+                self.source[to] = self.source[from];
+            }
+        }
+    }
+
+    fn ignite_cell(&mut self, index: usize, igniter: DamageSource) -> bool {
+        if self.burning[index].is_some() || self.density[index] < self.config.min_density {
+            return false;
+        }
+        self.burning[index] = Some(Time::ZERO);
+        self.activate(index);
+        let known = self.source[index].is_some_and(|source| source.fraction.is_some());
+        if !known {
+            self.source[index] = Some(igniter);
+        }
+        self.ignited.push(index);
+        true
+    }
+
+    fn activate(&mut self, index: usize) {
+        if !self.is_active[index] {
+            self.is_active[index] = true;
+            self.active.push(index);
+        }
+    }
+
+    /// Indices of the cells with the centers within the radius,
+    /// always including the cell at the position itself.
+    fn cells_near(&self, position: Position, radius: Coord) -> Vec<usize> {
+        let center = self.to_cell(position);
+        let reach = vec2(
+            (radius / self.cell_size.x).as_f32().ceil() as i64,
+            (radius / self.cell_size.y).as_f32().ceil() as i64,
+        );
+        let mut result = vec![self.index(center)];
+        for dx in -reach.x..=reach.x {
+            for dy in -reach.y..=reach.y {
+                let cell = center + vec2(dx, dy);
+                if cell != center && self.cell_center(cell).distance(position) <= radius {
+                    result.push(self.index(cell));
+                }
+            }
+        }
+        result
+    }
+
+    fn to_cell(&self, position: Position) -> vec2<i64> {
+        let position = position.to_world();
+        self.wrap(vec2(
+            (position.x / self.cell_size.x).as_f32().floor() as i64,
+            (position.y / self.cell_size.y).as_f32().floor() as i64,
+        ))
+    }
+
+    fn cell(&self, index: usize) -> vec2<i64> {
+        let index = i64::try_from(index).unwrap();
+        vec2(index % self.grid_size.x, index / self.grid_size.x)
+    }
+
+    fn cell_center(&self, cell: vec2<i64>) -> Position {
+        let center = vec2(
+            (r32(cell.x as f32) + r32(0.5)) * self.cell_size.x,
+            (r32(cell.y as f32) + r32(0.5)) * self.cell_size.y,
+        );
+        Position::from_world(center, self.world_size)
+    }
+
+    fn wrap(&self, cell: vec2<i64>) -> vec2<i64> {
+        vec2(
+            cell.x.rem_euclid(self.grid_size.x),
+            cell.y.rem_euclid(self.grid_size.y),
+        )
+    }
+
+    fn index(&self, cell: vec2<i64>) -> usize {
+        let cell = self.wrap(cell);
+        usize::try_from(cell.y * self.grid_size.x + cell.x).unwrap()
+    }
+}
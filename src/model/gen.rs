@@ -8,6 +8,7 @@ impl Model {
             &config.foreground,
             &palette,
             self.config.world_size,
+            &mut self.rng,
             &mut self.blocks,
         );
 
@@ -16,6 +17,7 @@ impl Model {
             &config.background,
             &palette,
             self.config.world_size,
+            &mut self.rng,
             &mut self.background_blocks,
         );
     }
@@ -38,7 +40,14 @@ impl Model {
             blocks: vec![barrel],
         };
         let palette = self.theme.get_palette(&self.theme.level.foreground);
-        generate_blocks(&config, &palette, self.config.world_size, &mut self.blocks);
+        generate_blocks(
+            &config,
+            &palette,
+            self.config.world_size,
+            &mut self.rng,
+            &mut self.blocks,
+        );
+        self.flow_field.invalidate_obstacles();
     }
 }
 
@@ -46,10 +55,9 @@ fn generate_blocks(
     config: &ProcGenConfig,
     palette: &[Color],
     world_size: vec2<Coord>,
+    rng: &mut impl Rng,
     result: &mut StructOf<Arena<Block>>,
 ) {
-    let mut rng = thread_rng();
-
     let max_iter = config.blocks_number * 5; // ~5 attempts per block
     let mut added = 0;
     for _ in 0..max_iter {
@@ -57,7 +65,7 @@ fn generate_blocks(
             break;
         }
 
-        let position = Position::random(&mut rng, world_size);
+        let position = Position::random(rng, world_size);
         if result
             .collider
             .position
@@ -70,13 +78,13 @@ fn generate_blocks(
 
         let block = config
             .blocks
-            .choose_weighted(&mut rng, |config| config.weight.as_f32())
+            .choose_weighted(rng, |config| config.weight.as_f32())
             .expect("no block variants found to generate")
             .clone();
 
         let (color, rotation) = match block.kind {
             BlockKind::Obstacle => (
-                *palette.choose(&mut rng).expect("no colors in the pallete"),
+                *palette.choose(rng).expect("no colors in the pallete"),
                 Angle::from_degrees(rng.gen_range(0.0..360.0).as_r32()),
             ),
             BlockKind::Barrel => (Color::WHITE, Angle::ZERO),
@@ -85,7 +93,8 @@ fn generate_blocks(
         result.insert(Block {
             color,
             health: block.health.map(Health::new_max),
-            on_fire: None,
+            statuses: StatusEffects::default(),
+            flammability: block.flammability,
             vulnerability: block.vulnerability,
             kind: block.kind,
             collider: {
@@ -96,6 +105,7 @@ fn generate_blocks(
                 }
             },
             explosion: block.explosion,
+            last_hit: None,
         });
         added += 1;
     }
@@ -22,13 +22,19 @@ impl Model {
                     );
                     let Some(player) = player else { return };
 
-                    if player.gun.shot_delay <= Time::ZERO {
+                    if player.gun.is_ready() {
                         let pos = *player.position;
-                        player.gun.shot_delay = player.gun.config.shot_delay;
+                        player.gun.fire();
                         let config = player.gun.config.shot.clone();
                         let dir = pos.delta_to(target_pos);
                         *player.velocity -= dir.normalize_or_zero() * player.gun.config.recoil;
-                        self.shoot(pos, target_pos, Fraction::Player, config);
+                        self.shoot(
+                            pos,
+                            target_pos,
+                            Fraction::Player,
+                            Some(self.player.actor),
+                            config,
+                        );
                     }
                 }
             }
@@ -65,10 +71,16 @@ impl Model {
                     }
                     PlayerState::Barrel { .. } => PlayerState::Human,
                 };
+                self.game_events.push(GameEvent::PlayerStateChanged {
+                    state: self.player.state.clone(),
+                });
             }
             PlayerAction::BarrelDash => {
                 if let PlayerState::Barrel { last_gas } = self.player.state {
                     self.player.state = PlayerState::Human;
+                    self.game_events.push(GameEvent::PlayerStateChanged {
+                        state: self.player.state.clone(),
+                    });
 
                     struct PlayerRef<'a> {
                         velocity: &'a mut vec2<Coord>,
@@ -95,10 +107,21 @@ impl Model {
                                 ignite_gasoline: true,
                                 ..self.config.player.barrel_state.dash_explosion.clone()
                             },
+                            source: DamageSource::actor(
+                                self.player.actor,
+                                Fraction::Player,
+                                DamageMechanic::Explosion,
+                            ),
                         },
                     });
                 }
             }
+            PlayerAction::SwitchWeapon { offset } => self.switch_weapon(offset),
+            PlayerAction::Reload => {
+                if let Some(Some(gun)) = self.actors.gun.get_mut(self.player.actor) {
+                    gun.start_reload();
+                }
+            }
         }
     }
 }
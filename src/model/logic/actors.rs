@@ -1,60 +1,84 @@
 use super::*;
 
 impl Model {
-    pub(super) fn actors_ai(&mut self, _delta_time: Time) {
+    pub(super) fn actors_ai(&mut self, delta_time: Time) {
         struct ActorRef<'a> {
+            id: Id,
             position: &'a mut Position,
             rotation: &'a mut Angle<Coord>,
             velocity: &'a mut vec2<Coord>,
+            fraction: &'a Fraction,
             stats: &'a Stats,
             controller: &'a mut Controller,
             ai: &'a mut ActorAI,
             kind: &'a mut ActorKind,
             gun: &'a mut Option<Gun>,
-            stunned: &'a Option<Time>,
+            statuses: &'a StatusEffects,
         }
 
         let Some(player) = self.actors.get(self.player.actor) else {
             return;
         };
         let player = player.clone();
+        let player_pos = player.body.collider.position;
+        self.update_flow_field(player_pos);
+        let steering = self.steering(player_pos);
+
+        let mut enemies_alive = query!(self.actors, (&fraction))
+            .filter(|fraction| **fraction == Fraction::Enemy)
+            .count();
 
         let mut shots = Vec::new();
+        let mut summons: Vec<(Position, String)> = Vec::new();
+        let mut detonated: Vec<(Id, Fraction, Position, ExplosionConfig)> = Vec::new();
 
         for actor in query!(
             self.actors,
             ActorRef {
+                id,
                 position: &mut body.collider.position,
                 rotation: &mut body.collider.rotation,
                 velocity: &mut body.velocity,
+                fraction,
                 stats,
                 controller: &mut controller,
                 ai: &mut ai.Get.Some,
                 kind: &mut kind,
                 gun: &mut gun,
-                stunned,
+                statuses,
             }
         ) {
-            if actor.stunned.is_some() {
+            if actor.statuses.is_disabled() {
                 continue;
             }
 
-            let player_dir = actor.position.delta_to(player.body.collider.position);
-            // let player_dist = player_dir.len();
+            let player_dir = actor.position.delta_to(player_pos);
+            let player_dist = player_dir.len();
             let player_dir = player_dir.normalize_or_zero();
+            // Walk around the blocks, or straight at the player when close enough
+            let path_dir = self
+                .flow_field
+                .direction(*actor.position)
+                .unwrap_or(player_dir);
+            let steering_force = steering.get(&actor.id).copied().unwrap_or(vec2::ZERO);
+            let steer = |dir: vec2<Coord>| {
+                let dir = dir + steering_force;
+                // Allow slowing down, but not speeding up
+                if dir.len() > Coord::ONE {
+                    dir.normalize()
+                } else {
+                    dir
+                }
+            };
 
             match actor.ai {
                 ActorAI::Crawler => {
-                    actor.controller.target_velocity = player_dir * actor.stats.move_speed;
+                    actor.controller.target_velocity = steer(path_dir) * actor.stats.move_speed;
                 }
                 ActorAI::Ranger { preferred_distance } => {
-                    let target = player
-                        .body
-                        .collider
-                        .position
-                        .shifted(-player_dir * *preferred_distance);
-                    let target_dir = actor.position.delta_to(target).normalize_or_zero();
-                    actor.controller.target_velocity = target_dir * actor.stats.move_speed;
+                    let target_dir =
+                        keep_distance(*actor.position, player_pos, *preferred_distance, path_dir);
+                    actor.controller.target_velocity = steer(target_dir) * actor.stats.move_speed;
 
                     if let ActorKind::EnemyDeathStar = actor.kind {
                         *actor.rotation += Angle::from_degrees(
@@ -62,71 +86,226 @@ impl Model {
                         );
                     }
 
-                    if let Some(gun) = actor.gun {
-                        if gun.shot_delay <= Time::ZERO {
-                            gun.shot_delay = gun.config.shot_delay;
-                            let target_pos = player.body.collider.position;
-                            let dir = actor.position.delta_to(target_pos);
-                            *actor.velocity -= dir.normalize_or_zero() * gun.config.recoil;
-                            shots.push((
-                                *actor.position,
-                                target_pos,
-                                Fraction::Enemy,
-                                gun.config.shot.clone(),
-                            ));
+                    if let Some(shot) =
+                        ready_gun(actor.gun, *actor.position, actor.velocity, player_pos)
+                    {
+                        shots.push((*actor.position, player_pos, Fraction::Enemy, actor.id, shot));
+                    }
+                }
+                ActorAI::Charger {
+                    charge_distance,
+                    telegraph_time,
+                    dash_speed,
+                    dash_time,
+                    cooldown,
+                    state,
+                } => match state {
+                    ChargerState::Chasing => {
+                        actor.controller.target_velocity = steer(path_dir) * actor.stats.move_speed;
+                        if player_dist <= *charge_distance {
+                            *state = ChargerState::Telegraphing {
+                                time_left: *telegraph_time,
+                                direction: player_dir,
+                            };
+                        }
+                    }
+                    ChargerState::Telegraphing {
+                        time_left,
+                        direction,
+                    } => {
+                        // Stand still and keep aiming
+                        actor.controller.target_velocity = vec2::ZERO;
+                        *direction = player_dir;
+                        *time_left -= delta_time;
+                        if *time_left <= Time::ZERO {
+                            *state = ChargerState::Dashing {
+                                time_left: *dash_time,
+                                direction: *direction,
+                            };
+                        }
+                    }
+                    ChargerState::Dashing {
+                        time_left,
+                        direction,
+                    } => {
+                        let velocity = *direction * *dash_speed;
+                        *actor.velocity = velocity;
+                        actor.controller.target_velocity = velocity;
+                        *time_left -= delta_time;
+                        if *time_left <= Time::ZERO {
+                            *state = ChargerState::Recovering {
+                                time_left: *cooldown,
+                            };
                         }
                     }
+                    ChargerState::Recovering { time_left } => {
+                        actor.controller.target_velocity = steer(path_dir) * actor.stats.move_speed;
+                        *time_left -= delta_time;
+                        if *time_left <= Time::ZERO {
+                            *state = ChargerState::Chasing;
+                        }
+                    }
+                },
+                ActorAI::Kamikaze {
+                    trigger_distance,
+                    explosion,
+                } => {
+                    actor.controller.target_velocity = steer(path_dir) * actor.stats.move_speed;
+                    if player_dist <= *trigger_distance {
+                        detonated.push((
+                            actor.id,
+                            *actor.fraction,
+                            *actor.position,
+                            explosion.clone(),
+                        ));
+                    }
                 }
-                ActorAI::BossFoot { position } => {
-                    *actor.velocity = vec2::ZERO;
-
-                    let sign = position.as_dir().x.signum();
-                    let rotation = Angle::from_radians(
-                        r32((self.time.as_f32() * 3.0).sin() * 0.8 - 0.2) * sign,
-                    );
-                    let point = vec2(-7.0 * -sign.as_f32(), -3.0).as_r32();
+                ActorAI::Orbiter { radius, clockwise } => {
+                    let dir = if player_dist > *radius * r32(1.5) {
+                        path_dir
+                    } else {
+                        // Move along the circle, correcting the distance to it
+                        let outward = -player_dir;
+                        let tangent = if *clockwise {
+                            -outward.rotate_90()
+                        } else {
+                            outward.rotate_90()
+                        };
+                        let correction = outward * (*radius - player_dist) / *radius;
+                        (tangent + correction).normalize_or_zero()
+                    };
+                    actor.controller.target_velocity = steer(dir) * actor.stats.move_speed;
 
-                    *actor.position = position.shifted(point.rotate(rotation));
-                    // *actor.position = Position::from_world(point, self.config.world_size);
-                    *actor.rotation = rotation;
+                    if let Some(shot) =
+                        ready_gun(actor.gun, *actor.position, actor.velocity, player_pos)
+                    {
+                        shots.push((*actor.position, player_pos, Fraction::Enemy, actor.id, shot));
+                    }
+                }
+                ActorAI::Summoner {
+                    preferred_distance,
+                    minions,
+                    summon_delay,
+                    summon_radius,
+                    max_enemies,
+                    cooldown,
+                } => {
+                    let dir =
+                        keep_distance(*actor.position, player_pos, *preferred_distance, path_dir);
+                    actor.controller.target_velocity = steer(dir) * actor.stats.move_speed;
 
-                    if actor.rotation.as_radians().abs() > r32(0.99) {
-                        let target_pos = player.body.collider.position;
-                        shots.push((
-                            *actor.position,
-                            target_pos,
-                            Fraction::Enemy,
-                            ShotConfig {
-                                pattern: ShotPattern::Multiple {
-                                    spread_degrees: r32(270.0),
-                                    bullets: 9,
-                                },
-                                projectile: ProjectileConfig {
-                                    lifetime: r32(5.0),
-                                    speed: r32(25.0),
-                                    damage: r32(15.0),
-                                    body: BodyConfig {
-                                        shape: Shape::Circle { radius: r32(0.2) },
-                                        mass: R32::ONE,
-                                    },
-                                    ai: ProjectileAI::ConstantTurn {
-                                        degrees_per_second: r32(90.0),
-                                    },
-                                    kind: ProjectileKind::SquidLike,
-                                    knockback: r32(10.0),
-                                },
-                            },
-                        ))
+                    *cooldown = (*cooldown - delta_time).max(Time::ZERO);
+                    if *cooldown <= Time::ZERO && enemies_alive < *max_enemies {
+                        if let Some(name) = minions.choose(&mut self.rng) {
+                            *cooldown = *summon_delay;
+                            enemies_alive += 1;
+                            let offset = self.rng.gen_circle(vec2::ZERO, *summon_radius);
+                            summons.push((actor.position.shifted(offset), name.clone()));
+                        }
                     }
                 }
-                ActorAI::BossBody => {
+                ActorAI::Sniper {
+                    preferred_distance,
+                    aim_time,
+                    state,
+                } => match state {
+                    SniperState::Moving => {
+                        let dir = keep_distance(
+                            *actor.position,
+                            player_pos,
+                            *preferred_distance,
+                            path_dir,
+                        );
+                        actor.controller.target_velocity = steer(dir) * actor.stats.move_speed;
+
+                        let gun_ready = actor.gun.as_ref().is_some_and(Gun::is_ready);
+                        if gun_ready && player_dist <= *preferred_distance * r32(1.5) {
+                            *state = SniperState::Aiming {
+                                time_left: *aim_time,
+                                target: player_pos,
+                            };
+                        }
+                    }
+                    SniperState::Aiming { time_left, target } => {
+                        actor.controller.target_velocity = vec2::ZERO;
+                        *time_left -= delta_time;
+                        if *time_left <= Time::ZERO {
+                            let target = *target;
+                            if let Some(shot) =
+                                ready_gun(actor.gun, *actor.position, actor.velocity, target)
+                            {
+                                shots.push((
+                                    *actor.position,
+                                    target,
+                                    Fraction::Enemy,
+                                    actor.id,
+                                    shot,
+                                ));
+                            }
+                            *state = SniperState::Moving;
+                        }
+                    }
+                },
+                ActorAI::BossPart(part) => {
                     *actor.velocity = vec2::ZERO;
+                    let (position, rotation) = part.motion.transform(part.anchor, self.time);
+                    *actor.position = position;
+                    *actor.rotation = rotation;
+
+                    for attack in &mut part.attacks {
+                        let ready = match attack.trigger {
+                            AttackTrigger::Periodic { delay } => {
+                                attack.cooldown -= delta_time;
+                                if attack.cooldown <= Time::ZERO {
+                                    attack.cooldown += delay;
+                                    true
+                                } else {
+                                    false
+                                }
+                            }
+                            AttackTrigger::SwingAngle { min_radians } => {
+                                rotation.as_radians().abs() > min_radians
+                            }
+                        };
+                        if ready {
+                            shots.push((
+                                *actor.position,
+                                player_pos,
+                                Fraction::Enemy,
+                                actor.id,
+                                attack.shot.clone(),
+                            ));
+                        }
+                    }
                 }
             }
         }
 
-        for (pos, aimed_towards, fraction, config) in shots {
-            self.shoot(pos, aimed_towards, fraction, config);
+        for (pos, aimed_towards, fraction, owner, config) in shots {
+            self.shoot(pos, aimed_towards, fraction, Some(owner), config);
+        }
+
+        for (position, name) in summons {
+            let config = self
+                .enemies_list
+                .get(&name)
+                .unwrap_or_else(|| panic!("Enemy {:?} not found", name))
+                .clone();
+            self.actors.insert(Actor::new_enemy(position, config));
+        }
+
+        for (id, fraction, position, config) in detonated {
+            // Despawn without dying, so the suicide does not count as a kill
+            // and does not trigger the death explosion or the drops
+            self.actors.remove(id);
+            let source = DamageSource::actor(id, fraction, DamageMechanic::Explosion);
+            self.queued_effects.push_back(QueuedEffect {
+                effect: Effect::Explosion {
+                    position,
+                    config,
+                    source,
+                },
+            });
         }
     }
 
@@ -134,7 +313,8 @@ impl Model {
         struct ActorRef<'a> {
             velocity: &'a mut vec2<Coord>,
             controller: &'a Controller,
-            stunned: &'a mut Option<Time>,
+            statuses: &'a StatusEffects,
+            invulnerable: &'a mut Option<Time>,
         }
 
         for actor in query!(
@@ -142,17 +322,21 @@ impl Model {
             ActorRef {
                 velocity: &mut body.velocity,
                 controller,
-                stunned: &mut stunned,
+                statuses,
+                invulnerable: &mut invulnerable,
             }
         ) {
-            let target_velocity = if let Some(time) = actor.stunned {
+            if let Some(time) = actor.invulnerable {
                 *time -= delta_time;
                 if *time <= Time::ZERO {
-                    *actor.stunned = None;
+                    *actor.invulnerable = None;
                 }
+            }
+
+            let target_velocity = if actor.statuses.is_disabled() {
                 vec2::ZERO
             } else {
-                actor.controller.target_velocity
+                actor.controller.target_velocity * actor.statuses.speed_multiplier()
             };
 
             // Interpolate body velocity to target velocity.
@@ -162,3 +346,35 @@ impl Model {
         }
     }
 }
+
+/// Direction to move in to keep the preferred distance from the target.
+/// Follows the path when too far away.
+fn keep_distance(
+    position: Position,
+    target: Position,
+    preferred_distance: Coord,
+    path_dir: vec2<Coord>,
+) -> vec2<Coord> {
+    let delta = position.delta_to(target);
+    if delta.len() > preferred_distance {
+        return path_dir;
+    }
+    let point = target.shifted(-delta.normalize_or_zero() * preferred_distance);
+    position.delta_to(point).normalize_or_zero()
+}
+
+/// Fire the gun and apply the recoil, if the gun is ready to shoot at the target.
+fn ready_gun(
+    gun: &mut Option<Gun>,
+    position: Position,
+    velocity: &mut vec2<Coord>,
+    target: Position,
+) -> Option<ShotConfig> {
+    let gun = gun.as_mut()?;
+    if !gun.is_ready() {
+        return None;
+    }
+    gun.fire();
+    *velocity -= position.delta_to(target).normalize_or_zero() * gun.config.recoil;
+    Some(gun.config.shot.clone())
+}
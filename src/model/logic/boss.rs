@@ -0,0 +1,121 @@
+use super::*;
+
+impl Model {
+    /// Switch the boss phases and spawn the minions.
+    pub(super) fn update_boss(&mut self, delta_time: Time) {
+        let Some(boss) = &mut self.boss else {
+            return;
+        };
+
+        let health: Option<Hp> = query!(self.actors, (&health, &ai.Get.Some))
+            .filter(|(_, ai)| matches!(ai, ActorAI::BossPart(_)))
+            .map(|(health, _)| health.value())
+            .reduce(|sum, health| sum + health);
+        let Some(health) = health else {
+            // The boss is dead
+            self.boss = None;
+            return;
+        };
+
+        boss.phase_time += delta_time;
+        if let Some(next) = boss.config.phases.get(boss.phase) {
+            let triggered = match next.trigger {
+                PhaseTrigger::Health { ratio } => health <= boss.max_health * ratio,
+                PhaseTrigger::Timer { time } => boss.phase_time >= time,
+            };
+            if triggered {
+                self.next_boss_phase();
+            }
+        }
+
+        self.spawn_minions(delta_time);
+    }
+
+    fn next_boss_phase(&mut self) {
+        struct PartRef<'a> {
+            ai: &'a mut ActorAI,
+            stats: &'a mut Stats,
+        }
+
+        let Some(boss) = &mut self.boss else {
+            return;
+        };
+        let Some(phase) = boss.config.phases.get(boss.phase).cloned() else {
+            return;
+        };
+        boss.phase += 1;
+        boss.phase_time = Time::ZERO;
+        boss.minion_cooldown = Time::ZERO;
+
+        for part in query!(
+            self.actors,
+            PartRef {
+                ai: &mut ai.Get.Some,
+                stats: &mut stats,
+            }
+        ) {
+            let ActorAI::BossPart(boss_part) = part.ai else {
+                continue;
+            };
+            let Some(changes) = phase.parts.get(&boss_part.name) else {
+                continue;
+            };
+            if let Some(motion) = &changes.motion {
+                boss_part.motion = motion.clone();
+            }
+            if let Some(attacks) = &changes.attacks {
+                boss_part.attacks = boss
+                    .config
+                    .attacks(attacks)
+                    .expect("boss shots are checked when loading");
+            }
+            if let Some(vulnerability) = &changes.vulnerability {
+                part.stats.vulnerability = vulnerability.clone();
+            }
+        }
+
+        if let Some(shake) = phase.screen_shake {
+            self.queued_effects.push_back(QueuedEffect {
+                effect: Effect::ScreenShake(shake),
+            });
+        }
+        self.game_events.push(GameEvent::BossPhaseChanged {
+            phase: boss.phase,
+            name: phase.name,
+            position: boss.position,
+        });
+    }
+
+    fn spawn_minions(&mut self, delta_time: Time) {
+        let Some(boss) = &mut self.boss else {
+            return;
+        };
+        let Some(minions) = boss.current_phase().and_then(|phase| phase.minions.clone()) else {
+            return;
+        };
+
+        boss.minion_cooldown -= delta_time;
+        if boss.minion_cooldown > Time::ZERO {
+            return;
+        }
+        boss.minion_cooldown = minions.delay;
+
+        let enemies_alive = query!(self.actors, (&fraction))
+            .filter(|fraction| **fraction == Fraction::Enemy)
+            .count();
+        if enemies_alive >= minions.max_enemies {
+            return;
+        }
+        let Some(name) = minions.enemies.choose(&mut self.rng) else {
+            return;
+        };
+        let config = self
+            .enemies_list
+            .get(name)
+            .unwrap_or_else(|| panic!("Enemy {:?} not found", name))
+            .clone();
+        let offset = self.rng.gen_circle(vec2::ZERO, minions.radius);
+        self.actors
+            .insert(Actor::new_enemy(boss.position.shifted(offset), config));
+    }
+}
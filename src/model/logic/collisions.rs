@@ -9,6 +9,51 @@ impl Model {
         self.projectile_gas(delta_time);
         self.fire_gas(delta_time);
         self.fire(delta_time);
+        self.spread_fire(delta_time);
+        self.update_oil(delta_time);
+        for contacts in [&mut self.contacts, &mut self.barrel_contacts] {
+            for pair in contacts.update(delta_time) {
+                self.game_events.push(GameEvent::ContactExited { pair });
+            }
+        }
+        self.block_contacts.update(delta_time);
+    }
+
+    /// Broadphase for the collisions with actors.
+    pub(super) fn actors_hash(&self) -> SpatialHash {
+        SpatialHash::from_colliders(
+            self.config.world_size,
+            self.config.broadphase_cell_size,
+            query!(self.actors, (id, &body.collider)).map(|(id, collider)| (id, collider.clone())),
+        )
+    }
+
+    /// Broadphase for the collisions with blocks.
+    pub(super) fn blocks_hash(&self) -> SpatialHash {
+        SpatialHash::from_colliders(
+            self.config.world_size,
+            self.config.broadphase_cell_size,
+            query!(self.blocks, (id, &collider)).map(|(id, collider)| (id, collider.clone())),
+        )
+    }
+
+    /// Broadphase for the collisions with projectiles.
+    fn projectiles_hash(&self) -> SpatialHash {
+        SpatialHash::from_colliders(
+            self.config.world_size,
+            self.config.broadphase_cell_size,
+            query!(self.projectiles, (id, &body.collider))
+                .map(|(id, collider)| (id, collider.clone())),
+        )
+    }
+
+    /// Broadphase for the collisions with fire.
+    fn fire_hash(&self) -> SpatialHash {
+        SpatialHash::from_colliders(
+            self.config.world_size,
+            self.config.broadphase_cell_size,
+            query!(self.fire, (id, &collider)).map(|(id, collider)| (id, collider.clone())),
+        )
     }
 
     fn collide_player(&mut self, delta_time: Time) {
@@ -50,6 +95,10 @@ impl Model {
             for id in picked_up {
                 if player.health.value() < player.health.max() {
                     let pickup = self.pickups.remove(id).unwrap();
+                    self.game_events.push(GameEvent::PickupCollected {
+                        kind: pickup.kind.clone(),
+                        position: pickup.body.collider.position,
+                    });
                     // TODO: as effect
                     match pickup.kind {
                         PickUpKind::Heal { hp } => {
@@ -73,12 +122,14 @@ impl Model {
     }
 
     fn collide_player_human(&mut self, _delta_time: Time) {
+        let actors_hash = self.actors_hash();
+
         struct ActorRef<'a> {
             id: Id,
+            fraction: &'a Fraction,
             collider: ColliderRef<'a>,
             velocity: &'a vec2<Coord>,
             stats: &'a Stats,
-            health: &'a Health,
         }
 
         let player = get!(
@@ -86,10 +137,10 @@ impl Model {
             self.player.actor,
             ActorRef {
                 id,
+                fraction,
                 collider: &body.collider,
                 velocity: &body.velocity,
                 stats,
-                health,
             }
         );
         let Some(player) = player else { return };
@@ -99,24 +150,33 @@ impl Model {
         struct Correction {
             position: Position,
             velocity: vec2<Coord>,
-            health: Health,
         }
         let mut corrections: HashMap<Id, Correction> = HashMap::new();
+        let mut hits: Vec<(DamageTarget, Hp, DamageSource)> = Vec::new();
 
-        for actor in query!(
-            self.actors,
-            ActorRef {
-                id,
-                collider: &body.collider,
-                velocity: &body.velocity,
-                stats,
-                health,
-            }
-        ) {
-            if actor.id == self.player.actor {
+        for actor_id in actors_hash.query(player_collider) {
+            if actor_id == self.player.actor {
                 continue;
             }
+            let actor = get!(
+                self.actors,
+                actor_id,
+                ActorRef {
+                    id,
+                    fraction,
+                    collider: &body.collider,
+                    velocity: &body.velocity,
+                    stats,
+                }
+            )
+            .unwrap();
             if let Some(collision) = player_collider.collide(&actor.collider.clone()) {
+                let pair = (self.player.actor, actor.id);
+                let phase = self.contacts.touch(pair);
+                if phase == ContactPhase::Enter {
+                    self.game_events.push(GameEvent::ContactEntered { pair });
+                }
+
                 let mut player_cor =
                     corrections
                         .get(&self.player.actor)
@@ -124,12 +184,10 @@ impl Model {
                         .unwrap_or(Correction {
                             position: *player.collider.position,
                             velocity: *player.velocity,
-                            health: *player.health,
                         });
                 let mut actor_cor = corrections.get(&actor.id).cloned().unwrap_or(Correction {
                     position: *actor.collider.position,
                     velocity: *actor.velocity,
-                    health: *actor.health,
                 });
 
                 let relative_vel = player_cor.velocity - *actor.velocity;
@@ -151,26 +209,26 @@ impl Model {
                         / (coef_player + coef_actor),
                 );
 
-                // Apply impulses
-                let hit_strength = dot.min(r32(10.0));
-                player_cor.velocity -= collision.normal * hit_strength * coef_player;
-                actor_cor.velocity += collision.normal * hit_strength * coef_actor;
+                // Apply impulses once per contact
+                if phase == ContactPhase::Enter {
+                    let hit_strength = dot.min(r32(10.0));
+                    player_cor.velocity -= collision.normal * hit_strength * coef_player;
+                    actor_cor.velocity += collision.normal * hit_strength * coef_actor;
+                }
 
-                if dot > r32(10.0) {
+                if dot > r32(10.0) && self.contacts.is_ready(pair) {
                     // Contact damage
-                    let damage_actor =
-                        player.stats.contact_damage * actor.stats.vulnerability.physical;
-                    let damage_player =
-                        actor.stats.contact_damage * player.stats.vulnerability.physical;
-                    player_cor.health.change(-damage_player);
-                    actor_cor.health.change(-damage_actor);
-
-                    self.queued_effects.push_back(QueuedEffect {
-                        effect: Effect::particles_damage(player_cor.position, damage_player),
-                    });
-                    self.queued_effects.push_back(QueuedEffect {
-                        effect: Effect::particles_damage(actor_cor.position, damage_actor),
-                    });
+                    self.contacts.hit(pair, self.config.contact_cooldown);
+                    hits.push((
+                        DamageTarget::Actor(self.player.actor),
+                        actor.stats.contact_damage,
+                        DamageSource::actor(actor.id, *actor.fraction, DamageMechanic::Contact),
+                    ));
+                    hits.push((
+                        DamageTarget::Actor(actor.id),
+                        player.stats.contact_damage,
+                        DamageSource::actor(player.id, *player.fraction, DamageMechanic::Contact),
+                    ));
                 }
 
                 corrections.insert(self.player.actor, player_cor);
@@ -181,7 +239,6 @@ impl Model {
         struct UpdateRef<'a> {
             position: &'a mut Position,
             velocity: &'a mut vec2<Coord>,
-            health: &'a mut Health,
         }
 
         for (id, correction) in corrections {
@@ -191,24 +248,26 @@ impl Model {
                 UpdateRef {
                     position: &mut body.collider.position,
                     velocity: &mut body.velocity,
-                    health: &mut health
                 }
             )
             .expect("invalid correction");
             *actor.position = correction.position;
             *actor.velocity = correction.velocity;
-            *actor.health = correction.health;
+        }
+
+        for (target, damage, source) in hits {
+            self.damage(target, damage, DamageType::Physical, source);
         }
     }
 
     fn collide_player_barrel(&mut self, _delta_time: Time) {
+        let actors_hash = self.actors_hash();
+
         struct ActorRef<'a> {
             id: Id,
             collider: ColliderRef<'a>,
             velocity: &'a vec2<Coord>,
-            stats: &'a Stats,
             stops_barrel: &'a bool,
-            health: &'a Health,
         }
 
         let player = get!(
@@ -218,9 +277,7 @@ impl Model {
                 id,
                 collider: &body.collider,
                 velocity: &body.velocity,
-                stats,
                 stops_barrel,
-                health,
             }
         );
         let Some(player) = player else { return };
@@ -231,25 +288,31 @@ impl Model {
             position: Position,
             velocity: vec2<Coord>,
             stun: Option<Time>,
-            health: Health,
         }
         let mut corrections: HashMap<Id, Correction> = HashMap::new();
+        let mut hits: Vec<(DamageTarget, Hp, DamageSource)> = Vec::new();
 
-        for actor in query!(
-            self.actors,
-            ActorRef {
-                id,
-                collider: &body.collider,
-                velocity: &body.velocity,
-                stats,
-                stops_barrel,
-                health,
-            }
-        ) {
-            if actor.id == self.player.actor {
+        for actor_id in actors_hash.query(player_collider) {
+            if actor_id == self.player.actor {
                 continue;
             }
+            let actor = get!(
+                self.actors,
+                actor_id,
+                ActorRef {
+                    id,
+                    collider: &body.collider,
+                    velocity: &body.velocity,
+                    stops_barrel,
+                }
+            )
+            .unwrap();
             if let Some(collision) = player_collider.collide(&actor.collider.clone()) {
+                let pair = (self.player.actor, actor.id);
+                if self.barrel_contacts.touch(pair) == ContactPhase::Enter {
+                    self.game_events.push(GameEvent::ContactEntered { pair });
+                }
+
                 let mut player_cor =
                     corrections
                         .get(&self.player.actor)
@@ -258,13 +321,11 @@ impl Model {
                             position: *player.collider.position,
                             velocity: *player.velocity,
                             stun: None,
-                            health: *player.health,
                         });
                 let mut actor_cor = corrections.get(&actor.id).cloned().unwrap_or(Correction {
                     position: *actor.collider.position,
                     velocity: *actor.velocity,
                     stun: None,
-                    health: *actor.health,
                 });
 
                 let relative_vel = player_cor.velocity - *actor.velocity;
@@ -281,23 +342,25 @@ impl Model {
                 };
                 player_cor.velocity -= collision.normal * hit_barrel;
 
-                // TODO: fix double damage (with on collision enter or smth)
-                // Runover damage
-                let damage = self.config.player.barrel_state.runover_damage
-                    + self.config.player.barrel_state.runover_damage_scale * relative_vel.len();
-                let actor_damage = damage * actor.stats.vulnerability.physical;
-                actor_cor.health.change(-actor_damage);
-
-                self.queued_effects.push_back(QueuedEffect {
-                    effect: Effect::particles_damage(actor_cor.position, actor_damage),
-                });
-
-                // TODO: configurable + better formula
-                actor_cor.stun = if *actor.stops_barrel {
-                    None
-                } else {
-                    Some(r32(3.0))
-                };
+                if self.barrel_contacts.is_ready(pair) {
+                    self.barrel_contacts.hit(pair, self.config.contact_cooldown);
+
+                    // Runover damage
+                    let damage = self.config.player.barrel_state.runover_damage
+                        + self.config.player.barrel_state.runover_damage_scale * relative_vel.len();
+                    hits.push((
+                        DamageTarget::Actor(actor.id),
+                        damage,
+                        DamageSource::actor(player.id, Fraction::Player, DamageMechanic::Runover),
+                    ));
+
+                    // TODO: configurable + better formula
+                    actor_cor.stun = if *actor.stops_barrel {
+                        None
+                    } else {
+                        Some(r32(3.0))
+                    };
+                }
 
                 corrections.insert(self.player.actor, player_cor);
                 corrections.insert(actor.id, actor_cor);
@@ -307,8 +370,7 @@ impl Model {
         struct UpdateRef<'a> {
             position: &'a mut Position,
             velocity: &'a mut vec2<Coord>,
-            stunned: &'a mut Option<Time>,
-            health: &'a mut Health,
+            statuses: &'a mut StatusEffects,
         }
 
         for (id, correction) in corrections {
@@ -318,15 +380,24 @@ impl Model {
                 UpdateRef {
                     position: &mut body.collider.position,
                     velocity: &mut body.velocity,
-                    stunned: &mut stunned,
-                    health: &mut health,
+                    statuses: &mut statuses,
                 }
             )
             .expect("invalid correction");
             *actor.position = correction.position;
             *actor.velocity = correction.velocity;
-            *actor.stunned = std::cmp::max(*actor.stunned, correction.stun);
-            *actor.health = correction.health;
+            if let Some(duration) = correction.stun {
+                actor.statuses.apply(StatusEffect {
+                    kind: StatusKind::Stunned,
+                    duration,
+                    stacking: Stacking::Refresh,
+                    source: None,
+                });
+            }
+        }
+
+        for (target, damage, source) in hits {
+            self.damage(target, damage, DamageType::Physical, source);
         }
     }
 
@@ -343,6 +414,7 @@ impl Model {
             velocity: vec2<Coord>,
         }
 
+        let actors_hash = self.actors_hash();
         let mut corrections: HashMap<Id, Correction> = HashMap::new();
 
         for actor in query!(
@@ -363,17 +435,20 @@ impl Model {
                 velocity: *actor.velocity,
             });
 
-            for other in query!(
-                self.actors,
-                ActorRef {
-                    id,
-                    collider: &body.collider,
-                    velocity: &body.velocity,
-                }
-            ) {
-                if other.id == self.player.actor || other.id <= actor.id {
+            for other_id in actors_hash.query(&actor_collider) {
+                if other_id == self.player.actor || other_id <= actor.id {
                     continue;
                 }
+                let other = get!(
+                    self.actors,
+                    other_id,
+                    ActorRef {
+                        id,
+                        collider: &body.collider,
+                        velocity: &body.velocity,
+                    }
+                )
+                .unwrap();
 
                 let mut other_collider = other.collider.clone();
                 let mut other_cor = corrections.get(&other.id).cloned().unwrap_or(Correction {
@@ -416,51 +491,77 @@ impl Model {
         struct ProjRef<'a> {
             id: Id,
             fraction: &'a Fraction,
+            owner: &'a Option<Id>,
             collider: ColliderRef<'a>,
             velocity: &'a mut vec2<Coord>,
             damage: &'a Hp,
             knockback: &'a Coord,
+            statuses: &'a Vec<StatusEffect>,
+            pierce: &'a mut usize,
+            pierced: &'a mut Vec<Id>,
+            thrown: &'a Option<Thrown>,
         }
 
         struct ActorRef<'a> {
+            id: Id,
             fraction: &'a Fraction,
             collider: ColliderRef<'a>,
             velocity: &'a mut vec2<Coord>,
-            health: &'a mut Health,
-            stats: &'a Stats,
         }
 
+        let actors_hash = self.actors_hash();
         let mut proj_hits: Vec<Id> = Vec::new();
+        let mut hits: Vec<(DamageTarget, Hp, DamageSource, Vec<StatusEffect>)> = Vec::new();
         for proj in query!(
             self.projectiles,
             ProjRef {
                 id,
                 fraction,
+                owner,
                 collider: &body.collider,
                 velocity: &mut body.velocity,
                 damage,
                 knockback,
+                statuses,
+                pierce: &mut pierce,
+                pierced: &mut pierced,
+                thrown,
             }
         ) {
-            for actor in query!(
-                self.actors,
-                ActorRef {
-                    fraction,
-                    collider: &body.collider,
-                    velocity: &mut body.velocity,
-                    health: &mut health,
-                    stats,
+            if proj.thrown.is_some() {
+                // Flies over the actors
+                continue;
+            }
+            for actor_id in actors_hash.query(&proj.collider.clone()) {
+                if proj.pierced.contains(&actor_id) {
+                    continue;
                 }
-            ) {
+                let actor = get!(
+                    self.actors,
+                    actor_id,
+                    ActorRef {
+                        id,
+                        fraction,
+                        collider: &body.collider,
+                        velocity: &mut body.velocity,
+                    }
+                )
+                .unwrap();
                 if proj.fraction == actor.fraction {
                     // Friendly fire
                     continue;
                 }
                 if proj.collider.clone().check(&actor.collider.clone()) {
-                    proj_hits.push(proj.id);
-                    actor
-                        .health
-                        .change(-*proj.damage * actor.stats.vulnerability.projectile);
+                    hits.push((
+                        DamageTarget::Actor(actor.id),
+                        *proj.damage,
+                        DamageSource {
+                            owner: *proj.owner,
+                            fraction: Some(*proj.fraction),
+                            mechanic: DamageMechanic::Projectile,
+                        },
+                        proj.statuses.clone(),
+                    ));
 
                     // If player is hit, switch back to human state
                     // if *actor.fraction == Fraction::Player {
@@ -473,10 +574,13 @@ impl Model {
                     // Knockback
                     *actor.velocity += relative_vel * r32(0.1) * *proj.knockback;
 
-                    self.queued_effects.push_back(QueuedEffect {
-                        effect: Effect::particles_damage(*actor.collider.position, *proj.damage),
-                    });
-
+                    if *proj.pierce > 0 {
+                        // Pass through
+                        *proj.pierce -= 1;
+                        proj.pierced.push(actor.id);
+                        continue;
+                    }
+                    proj_hits.push(proj.id);
                     break;
                 }
             }
@@ -485,38 +589,55 @@ impl Model {
         for id in proj_hits {
             self.projectiles.remove(id);
         }
+        for (target, damage, source, statuses) in hits {
+            self.damage(target, damage, DamageType::Projectile, source);
+            self.apply_statuses(target, &statuses, source);
+        }
     }
 
     fn collide_blocks(&mut self, _delta_time: Time) {
         struct BlockRef<'a> {
+            id: Id,
             collider: ColliderRef<'a>,
-            health: &'a mut Option<Health>,
-            vulnerability: &'a VulnerabilityStats,
         }
 
         // Actors
 
         struct ActorRef<'a> {
+            id: Id,
             collider: ColliderRefMut<'a>,
             velocity: &'a mut vec2<Coord>,
         }
 
+        // Only the player in barrel form rams the blocks
+        let ramming = match self.player.state {
+            PlayerState::Barrel { .. } => self.config.player.barrel_state.ramming.clone(),
+            PlayerState::Human => None,
+        };
+        if let Some(config) = &ramming {
+            self.ram_blocks(config);
+        }
+
+        let blocks_hash = self.blocks_hash();
         for actor in query!(
             self.actors,
             ActorRef {
+                id,
                 collider: &mut body.collider,
                 velocity: &mut body.velocity,
             }
         ) {
-            for block in query!(
-                self.blocks,
-                BlockRef {
-                    collider,
-                    health: &mut health,
-                    vulnerability,
-                }
-            ) {
+            for block_id in blocks_hash.query(&actor.collider.clone()) {
+                let block = get!(self.blocks, block_id, BlockRef { id, collider }).unwrap();
                 if let Some(collision) = actor.collider.clone().collide(&block.collider.clone()) {
+                    if ramming.is_some() && actor.id == self.player.actor {
+                        // Plow through the blocks destroyed by the impact
+                        let health = get!(self.blocks, block.id, (&health)).unwrap();
+                        if health.as_ref().is_some_and(Health::is_min) {
+                            continue;
+                        }
+                    }
+
                     actor
                         .collider
                         .position
@@ -534,38 +655,61 @@ impl Model {
         struct ProjRef<'a> {
             id: Id,
             collider: ColliderRefMut<'a>,
-            damage: &'a Hp,
+            velocity: &'a mut vec2<Coord>,
+            bounces: &'a mut usize,
+            thrown: &'a Option<Thrown>,
         }
 
-        let mut hit_projs: Vec<Id> = Vec::new();
+        // Projectiles that hit a block and whether they bounced off
+        let mut hit_projs: Vec<(Id, Id, bool)> = Vec::new();
         for proj in query!(
             self.projectiles,
             ProjRef {
                 id,
                 collider: &mut body.collider,
-                damage,
+                velocity: &mut body.velocity,
+                bounces: &mut bounces,
+                thrown,
             }
         ) {
-            for block in query!(
-                self.blocks,
-                BlockRef {
-                    collider,
-                    health: &mut health,
-                    vulnerability,
-                }
-            ) {
-                if proj.collider.clone().check(&block.collider.clone()) {
-                    hit_projs.push(proj.id);
-                    if let Some(health) = block.health {
-                        health.change(-*proj.damage * block.vulnerability.physical);
+            if proj.thrown.is_some() {
+                // Flies over the blocks
+                continue;
+            }
+            for block_id in blocks_hash.query(&proj.collider.clone()) {
+                let block = get!(self.blocks, block_id, BlockRef { id, collider }).unwrap();
+                let Some(collision) = proj.collider.clone().collide(&block.collider.clone()) else {
+                    continue;
+                };
+                let bounced = *proj.bounces > 0;
+                if bounced {
+                    *proj.bounces -= 1;
+                    proj.collider
+                        .position
+                        .shift(-collision.normal * collision.penetration);
+                    let dot = vec2::dot(collision.normal, *proj.velocity);
+                    if dot > Coord::ZERO {
+                        *proj.velocity -= collision.normal * dot * r32(2.0);
                     }
-                    break;
                 }
+                hit_projs.push((proj.id, block.id, bounced));
+                break;
             }
         }
 
-        for id in hit_projs {
-            let proj = self.projectiles.remove(id).unwrap();
+        for (proj_id, block_id, bounced) in hit_projs {
+            let proj = if bounced {
+                self.projectiles.get(proj_id).unwrap().clone()
+            } else {
+                self.projectiles.remove(proj_id).unwrap()
+            };
+            // Blocks take bullets as physical damage
+            self.damage(
+                DamageTarget::Block(block_id),
+                proj.damage,
+                DamageType::Physical,
+                proj.damage_source(),
+            );
             self.queued_effects.push_back(QueuedEffect {
                 effect: {
                     Effect::Particles {
@@ -582,61 +726,201 @@ impl Model {
         }
     }
 
-    /// Projectiles ignite gas when passing over it.
-    fn projectile_gas(&mut self, _delta_time: Time) {
-        if !self.config.bullets_ignite_on_hover {
+    /// Damage the blocks rammed by the player in barrel form, once per contact.
+    /// The player keeps some of the speed when the impact destroys the block.
+    fn ram_blocks(&mut self, config: &RammingConfig) {
+        let Some((collider, &velocity)) = get!(
+            self.actors,
+            self.player.actor,
+            (&body.collider, &body.velocity)
+        ) else {
             return;
-        }
+        };
+        let collider = collider.clone();
 
-        let mut gas_ignited: Vec<Id> = Vec::new();
+        let mut rams: Vec<BlockRam> = Vec::new();
+        for block_id in self.blocks_hash().query(&collider) {
+            let block_collider = get!(self.blocks, block_id, (&collider)).unwrap();
+            let Some(collision) = collider.collide(&block_collider.clone()) else {
+                continue;
+            };
+            let pair = (self.player.actor, block_id);
+            self.block_contacts.touch(pair);
+            let speed = vec2::dot(collision.normal, velocity);
+            if speed >= config.min_speed && self.block_contacts.is_ready(pair) {
+                self.block_contacts.hit(pair, self.config.contact_cooldown);
+                rams.push(BlockRam {
+                    block: block_id,
+                    speed,
+                    direction: collision.normal,
+                });
+            }
+        }
 
-        for (gas_id, gas) in query!(self.gasoline, (id, &collider)) {
-            for proj in query!(self.projectiles, (&body.collider)) {
-                if proj.clone().check(&gas.clone()) {
-                    gas_ignited.push(gas_id);
-                    break;
+        let source =
+            DamageSource::actor(self.player.actor, Fraction::Player, DamageMechanic::Runover);
+        let mut plowed = false;
+        for ram in rams {
+            let Some(block) = get!(self.blocks, ram.block, (&collider, &kind)) else {
+                // Already launched
+                continue;
+            };
+            let (collider, &kind) = block;
+            let collider = collider.clone();
+            let damage = match kind {
+                BlockKind::Obstacle => self.damage(
+                    DamageTarget::Block(ram.block),
+                    ram.speed * config.damage_per_speed,
+                    DamageType::Physical,
+                    source,
+                ),
+                BlockKind::Barrel => {
+                    self.hit_barrel(ram.block, ram.direction, config.barrel_impact, source);
+                    ram.speed * config.damage_per_speed
                 }
-            }
+            };
+            // Launched barrels are gone, the rest of the blocks are destroyed at zero health
+            plowed |= self
+                .blocks
+                .health
+                .get(ram.block)
+                .is_none_or(|health| health.as_ref().is_some_and(Health::is_min));
+
+            self.queued_effects.push_back(QueuedEffect {
+                effect: Effect::Particles {
+                    position: collider.position,
+                    position_radius: collider.shape.bounding_radius(),
+                    velocity: ram.direction * ram.speed * r32(0.3),
+                    size: r32(0.3),
+                    lifetime: r32(0.7),
+                    intensity: damage * config.debris,
+                    kind: ParticleKind::Debris,
+                },
+            });
         }
 
-        for id in gas_ignited {
-            self.ignite_gasoline(id);
+        if plowed {
+            if let Some(velocity) = get!(self.actors, self.player.actor, (&mut body.velocity)) {
+                *velocity *= config.plow_speed;
+            }
         }
     }
 
-    fn fire_gas(&mut self, delta_time: Time) {
-        struct FireRef<'a> {
-            collider: ColliderRef<'a>,
+    /// Detonate or launch the barrel block rammed in the direction.
+    fn hit_barrel(
+        &mut self,
+        block_id: Id,
+        direction: vec2<Coord>,
+        impact: BarrelImpact,
+        source: DamageSource,
+    ) {
+        match impact {
+            BarrelImpact::Detonate => {
+                // Explodes once removed as destroyed
+                if let Some((health, last_hit)) =
+                    get!(self.blocks, block_id, (&mut health.Get.Some, &mut last_hit))
+                {
+                    let max = health.max();
+                    health.change(-max);
+                    *last_hit = Some(source);
+                }
+            }
+            BarrelImpact::Launch {
+                distance,
+                speed,
+                arc_height,
+            } => {
+                let block = self.blocks.remove(block_id).unwrap();
+                self.flow_field.invalidate_obstacles();
+                self.game_events.push(GameEvent::BlockDestroyed {
+                    kind: block.kind,
+                    position: block.collider.position,
+                });
+                // A burning barrel keeps burning where it lands
+                let fire = block.statuses.burning().map(|_| FirePuddle {
+                    radius: block.collider.shape.bounding_radius(),
+                    fire: self.config.fire.clone(),
+                });
+                let spill = block.explosion.as_ref().map(|explosion| GasolineSpill {
+                    radius: explosion.radius / r32(3.0),
+                    amount: self.config.player.barrel_state.gasoline.amount,
+                });
+                let config = ProjectileConfig {
+                    lifetime: distance / speed,
+                    speed,
+                    damage: Hp::ZERO,
+                    body: BodyConfig {
+                        shape: block.collider.shape,
+                        mass: R32::ONE,
+                    },
+                    ai: ProjectileAI::Straight,
+                    kind: ProjectileKind::Barrel,
+                    knockback: Coord::ZERO,
+                    statuses: Vec::new(),
+                    bounces: 0,
+                    pierce: 0,
+                    speed_curve: Vec::new(),
+                    throw: Some(ThrowConfig {
+                        arc_height,
+                        explosion: block.explosion,
+                        ignite_radius: fire.as_ref().map(|fire| fire.radius),
+                        fire,
+                        spill,
+                    }),
+                };
+                let position = block.collider.position;
+                let target = position.shifted(direction.normalize_or_zero() * distance);
+                self.projectiles.insert(
+                    Projectile::new(position, direction.arg(), Fraction::Player, config)
+                        .with_owner(Some(self.player.actor))
+                        .with_target(target),
+                );
+                self.add_barrels(1); // Spawn a new barrel
+            }
         }
+    }
 
-        struct GasRef<'a> {
-            id: Id,
-            collider: ColliderRef<'a>,
-            ignite_timer: &'a mut Time,
+    /// Projectiles ignite gas when passing over it.
+    fn projectile_gas(&mut self, _delta_time: Time) {
+        if !self.config.bullets_ignite_on_hover {
+            return;
         }
 
-        let mut to_ignite: Vec<Id> = Vec::new();
-        for gas in query!(
-            self.gasoline,
-            GasRef {
-                id,
-                collider,
-                ignite_timer: &mut ignite_timer
-            }
-        ) {
-            for fire in query!(self.fire, FireRef { collider }) {
-                if fire.collider.clone().check(&gas.collider.clone()) {
-                    *gas.ignite_timer -= delta_time;
-                    if *gas.ignite_timer <= Time::ZERO {
-                        to_ignite.push(gas.id);
-                        break;
-                    }
-                }
-            }
+        let to_ignite: Vec<(Position, Coord, DamageSource)> = query!(
+            self.projectiles,
+            (&body.collider, &fraction, &owner, &thrown)
+        )
+        .filter(|(_, _, _, thrown)| thrown.is_none())
+        .map(|(collider, &fraction, &owner, _)| {
+            let igniter = DamageSource {
+                owner,
+                fraction: Some(fraction),
+                mechanic: DamageMechanic::Projectile,
+            };
+            (
+                *collider.position,
+                collider.shape.bounding_radius(),
+                igniter,
+            )
+        })
+        .collect();
+
+        for (position, radius, igniter) in to_ignite {
+            self.ignite_gasoline(position, radius, igniter);
         }
+    }
+
+    /// Fire on the ground ignites the gas under it.
+    fn fire_gas(&mut self, _delta_time: Time) {
+        let to_ignite: Vec<(Position, Coord, DamageSource)> =
+            query!(self.fire, (&collider, &source))
+                .map(|(collider, &source)| {
+                    (*collider.position, collider.shape.bounding_radius(), source)
+                })
+                .collect();
 
-        for gas_id in to_ignite {
-            self.ignite_gasoline(gas_id);
+        for (position, radius, igniter) in to_ignite {
+            self.ignite_gasoline(position, radius, igniter);
         }
     }
 
@@ -644,53 +928,89 @@ impl Model {
         struct FireRef<'a> {
             collider: ColliderRef<'a>,
             config: &'a FireConfig,
+            source: &'a DamageSource,
         }
 
         struct ActorRef<'a> {
             id: Id,
             collider: ColliderRef<'a>,
             velocity: &'a mut vec2<Coord>,
-            stats: &'a Stats,
-            on_fire: &'a mut Option<OnFire>,
         }
 
+        let fire_hash = self.fire_hash();
+        let gas_fire = &self.config.player.barrel_state.gasoline.fire;
+        let mut burning: Vec<(DamageTarget, StatusEffect)> = Vec::new();
         for actor in query!(
             self.actors,
             ActorRef {
                 id,
                 collider: &body.collider,
                 velocity: &mut body.velocity,
-                stats,
-                on_fire: &mut on_fire,
             }
         ) {
-            for fire in query!(self.fire, FireRef { collider, config }) {
-                if actor.collider.clone().check(&fire.collider.clone()) {
-                    if actor.stats.vulnerability.fire > R32::ZERO {
-                        *actor.on_fire = Some(update_on_fire(
-                            actor.on_fire.clone(),
-                            OnFire {
-                                duration: fire.config.duration,
-                                damage_per_second: fire.config.damage_per_second,
-                            },
-                        ));
-                    }
-                    if actor.id == self.player.actor {
-                        if let PlayerState::Barrel { .. } = self.player.state {
-                            // Explode the barrel
-                            // let dir = fire
-                            //     .collider
-                            //     .position
-                            //     .direction(*actor.collider.position, self.config.world_size)
-                            //     .normalize_or_zero();
-                            let dir = actor.velocity.normalize_or_zero();
-                            *actor.velocity +=
-                                dir * self.config.player.barrel_state.self_explosion_strength;
-                            self.player.state = PlayerState::Human;
+            let actor_collider = actor.collider.clone();
+            let mut in_fire = fire_hash
+                .query(&actor_collider)
+                .into_iter()
+                .find_map(|fire_id| {
+                    let fire = get!(
+                        self.fire,
+                        fire_id,
+                        FireRef {
+                            collider,
+                            config,
+                            source
                         }
-                    }
+                    )
+                    .unwrap();
+                    actor_collider
+                        .check(&fire.collider.clone())
+                        .then(|| fire.config.burning(Some(*fire.source)))
+                });
+            if in_fire.is_none() {
+                // Burning gasoline
+                in_fire = self
+                    .gasoline
+                    .burning_near(
+                        actor_collider.position,
+                        actor_collider.shape.bounding_radius(),
+                    )
+                    .map(|source| gas_fire.burning(Some(source)));
+            }
+            let Some(effect) = in_fire else {
+                continue;
+            };
+
+            burning.push((DamageTarget::Actor(actor.id), effect));
+            if actor.id == self.player.actor {
+                if let PlayerState::Barrel { .. } = self.player.state {
+                    // Explode the barrel
+                    // let dir = fire
+                    //     .collider
+                    //     .position
+                    //     .direction(*actor.collider.position, self.config.world_size)
+                    //     .normalize_or_zero();
+                    let dir = actor.velocity.normalize_or_zero();
+                    *actor.velocity +=
+                        dir * self.config.player.barrel_state.self_explosion_strength;
+                    self.player.state = PlayerState::Human;
+                    self.game_events.push(GameEvent::PlayerStateChanged {
+                        state: self.player.state.clone(),
+                    });
                 }
             }
         }
+
+        for (target, effect) in burning {
+            self.apply_status(target, effect);
+        }
     }
 }
+
+/// The player in barrel form rammed into a block.
+struct BlockRam {
+    block: Id,
+    /// Impact speed along the direction.
+    speed: Coord,
+    direction: vec2<Coord>,
+}
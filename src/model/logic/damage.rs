@@ -0,0 +1,144 @@
+use super::*;
+
+impl Model {
+    /// Deal damage to the target, scaled by its vulnerability to the damage type.
+    /// Invulnerable targets take no damage.
+    /// Returns the damage actually dealt.
+    pub fn damage(
+        &mut self,
+        target: DamageTarget,
+        amount: Hp,
+        damage_type: DamageType,
+        source: DamageSource,
+    ) -> Hp {
+        self.deal_damage(target, amount, damage_type, source, true)
+    }
+
+    /// Deal a tick of damage over time, like [Model::damage] but without the hit feedback:
+    /// no particles and no invulnerability for the player. The events are still reported.
+    pub fn damage_over_time(
+        &mut self,
+        target: DamageTarget,
+        amount: Hp,
+        damage_type: DamageType,
+        source: DamageSource,
+    ) -> Hp {
+        self.deal_damage(target, amount, damage_type, source, false)
+    }
+
+    fn deal_damage(
+        &mut self,
+        target: DamageTarget,
+        amount: Hp,
+        damage_type: DamageType,
+        source: DamageSource,
+        feedback: bool,
+    ) -> Hp {
+        let (damage, position) = match target {
+            DamageTarget::Actor(id) => {
+                struct ActorRef<'a> {
+                    position: &'a Position,
+                    health: &'a mut Health,
+                    stats: &'a Stats,
+                    invulnerable: &'a Option<Time>,
+                    statuses: &'a mut StatusEffects,
+                    last_hit: &'a mut Option<DamageSource>,
+                }
+
+                let actor = get!(
+                    self.actors,
+                    id,
+                    ActorRef {
+                        position: &body.collider.position,
+                        health: &mut health,
+                        stats,
+                        invulnerable,
+                        statuses: &mut statuses,
+                        last_hit: &mut last_hit,
+                    }
+                );
+                let Some(actor) = actor else {
+                    return Hp::ZERO;
+                };
+                if actor.invulnerable.is_some() {
+                    return Hp::ZERO;
+                }
+
+                let damage = amount
+                    * actor.stats.vulnerability.get(damage_type)
+                    * actor.statuses.vulnerability(damage_type);
+                let damage = actor.statuses.absorb(damage);
+                actor.health.change(-damage);
+                *actor.last_hit = Some(source);
+                (damage, *actor.position)
+            }
+            DamageTarget::Block(id) => {
+                struct BlockRef<'a> {
+                    position: &'a Position,
+                    health: &'a mut Health,
+                    vulnerability: &'a VulnerabilityStats,
+                    statuses: &'a mut StatusEffects,
+                    last_hit: &'a mut Option<DamageSource>,
+                }
+
+                let block = get!(
+                    self.blocks,
+                    id,
+                    BlockRef {
+                        position: &collider.position,
+                        health: &mut health.Get.Some,
+                        vulnerability,
+                        statuses: &mut statuses,
+                        last_hit: &mut last_hit,
+                    }
+                );
+                // Blocks without health are indestructible
+                let Some(block) = block else {
+                    return Hp::ZERO;
+                };
+
+                let damage = amount
+                    * block.vulnerability.get(damage_type)
+                    * block.statuses.vulnerability(damage_type);
+                let damage = block.statuses.absorb(damage);
+                block.health.change(-damage);
+                *block.last_hit = Some(source);
+                (damage, *block.position)
+            }
+        };
+
+        if damage <= Hp::ZERO {
+            return damage;
+        }
+
+        if feedback {
+            self.queued_effects.push_back(QueuedEffect {
+                effect: Effect::particles_damage(position, damage),
+            });
+        }
+        self.game_events.push(GameEvent::DamageDealt {
+            target,
+            position,
+            damage,
+            damage_type,
+            source,
+        });
+
+        if target == DamageTarget::Actor(self.player.actor) {
+            self.game_events.push(GameEvent::PlayerDamaged {
+                damage,
+                damage_type,
+                source,
+            });
+
+            let time = self.config.player.invulnerability_time;
+            if feedback && time > Time::ZERO {
+                if let Some(invulnerable) = self.actors.invulnerable.get_mut(self.player.actor) {
+                    *invulnerable = Some(time);
+                }
+            }
+        }
+
+        damage
+    }
+}
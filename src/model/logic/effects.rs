@@ -13,7 +13,11 @@ impl Model {
             Effect::ScreenShake(shake) => {
                 self.screen_shake.merge(shake);
             }
-            Effect::Explosion { position, config } => {
+            Effect::Explosion {
+                position,
+                config,
+                source,
+            } => {
                 self.explosions.insert(Explosion {
                     position,
                     max_radius: config.radius,
@@ -37,50 +41,52 @@ impl Model {
                     dir * strength
                 };
 
-                let calculate_damage = |body_position: Position, vulnerability: R32| -> Hp {
+                let calculate_damage = |body_position: Position| -> Hp {
                     let delta = position.delta_to(body_position);
                     let dist = delta.len();
                     let t = (Coord::ONE
                         - ((dist - Coord::ONE).max(Coord::ZERO) / config.radius).sqrt())
                     .clamp(Coord::ZERO, Coord::ONE);
-                    config.damage * t * vulnerability
+                    config.damage * t
                 };
 
                 {
                     // Update actors
                     struct ActorRef<'a> {
+                        id: Id,
+                        fraction: &'a Fraction,
                         position: &'a Position,
                         velocity: &'a mut vec2<Coord>,
-                        health: &'a mut Health,
-                        on_fire: &'a mut Option<OnFire>,
-                        stats: &'a Stats,
                     }
 
+                    let mut hits: Vec<(DamageTarget, Hp)> = Vec::new();
                     for actor in query!(
                         self.actors,
                         ActorRef {
+                            id,
+                            fraction,
                             position: &body.collider.position,
                             velocity: &mut body.velocity,
-                            health: &mut health,
-                            on_fire: &mut on_fire,
-                            stats,
                         }
                     ) {
                         if !check(*actor.position) {
                             continue;
                         }
                         *actor.velocity += apply_velocity(*actor.position);
-                        actor.health.change(-calculate_damage(
-                            *actor.position,
-                            actor.stats.vulnerability.explosive,
-                        ));
-                        // Ignite
-                        if let Some(mut fire) = config.ignite.clone() {
-                            if actor.stats.vulnerability.fire > R32::ZERO {
-                                fire.damage_per_second *= actor.stats.vulnerability.fire;
-                                *actor.on_fire = Some(update_on_fire(actor.on_fire.clone(), fire));
-                            }
+                        if !self.config.explosions_friendly_fire
+                            && source.fraction == Some(*actor.fraction)
+                        {
+                            continue;
                         }
+                        hits.push((
+                            DamageTarget::Actor(actor.id),
+                            calculate_damage(*actor.position),
+                        ));
+                    }
+                    for (target, damage) in hits {
+                        self.damage(target, damage, DamageType::Explosive, source);
+                        self.apply_statuses(target, &config.statuses, source);
+                        self.ignite_soaked(target, source);
                     }
 
                     // Screen shake
@@ -98,32 +104,32 @@ impl Model {
                 {
                     // Update blocks
                     struct BlockRef<'a> {
+                        id: Id,
                         position: &'a Position,
-                        health: &'a mut Health,
-                        on_fire: &'a mut Option<OnFire>,
-                        vulnerability: &'a VulnerabilityStats,
+                        health: &'a Option<Health>,
                     }
 
+                    let mut hits: Vec<(DamageTarget, Hp)> = Vec::new();
                     for block in query!(
                         self.blocks,
                         BlockRef {
+                            id,
                             position: &collider.position,
-                            health: &mut health.Get.Some,
-                            on_fire: &mut on_fire,
-                            vulnerability,
+                            health,
                         }
                     ) {
-                        if !check(*block.position) {
+                        // Indestructible blocks are not affected
+                        if block.health.is_none() || !check(*block.position) {
                             continue;
                         }
-                        block.health.change(-calculate_damage(
-                            *block.position,
-                            block.vulnerability.explosive,
+                        hits.push((
+                            DamageTarget::Block(block.id),
+                            calculate_damage(*block.position),
                         ));
-                        // Ignite
-                        if let Some(fire) = config.ignite.clone() {
-                            *block.on_fire = Some(update_on_fire(block.on_fire.clone(), fire));
-                        }
+                    }
+                    for (target, damage) in hits {
+                        self.damage(target, damage, DamageType::Explosive, source);
+                        self.apply_statuses(target, &config.statuses, source);
                     }
                 }
 
@@ -149,14 +155,7 @@ impl Model {
                 }
 
                 if config.ignite_gasoline {
-                    // Ignite gasoline
-                    let to_ignite: Vec<Id> = query!(self.gasoline, (id, &collider.position))
-                        .filter(|(_, &pos)| check(pos))
-                        .map(|(id, _)| id)
-                        .collect();
-                    for id in to_ignite {
-                        self.ignite_gasoline(id);
-                    }
+                    self.ignite_gasoline(position, config.radius, source);
                 }
 
                 // Sound
@@ -174,9 +173,8 @@ impl Model {
                 intensity,
                 kind: ai,
             } => {
-                let mut rng = thread_rng();
                 let amount = if intensity.as_f32() < 1.0 {
-                    usize::from(rng.gen_bool(intensity.as_f32().into()))
+                    usize::from(self.rng.gen_bool(intensity.as_f32().into()))
                 } else {
                     #[allow(clippy::cast_sign_loss)]
                     // `.max(0.0)` makes sure the value is not negative
@@ -185,7 +183,7 @@ impl Model {
                     }
                 };
                 for _ in 0..amount {
-                    let pos = rng.gen_circle(vec2::ZERO, position_radius);
+                    let pos = self.rng.gen_circle(vec2::ZERO, position_radius);
                     let pos = position.shifted(pos);
                     self.particles.insert(Particle {
                         position: pos,
@@ -0,0 +1,140 @@
+use super::*;
+
+impl Model {
+    /// Burning actors and blocks, and the fire on the ground,
+    /// set their flammable neighbours on fire after being in contact for a while.
+    pub(super) fn spread_fire(&mut self, delta_time: Time) {
+        struct Burning {
+            target: Option<DamageTarget>,
+            collider: Collider,
+            config: FireConfig,
+            source: Option<DamageSource>,
+        }
+
+        let mut burning: Vec<Burning> = Vec::new();
+        for (id, collider, statuses) in query!(self.actors, (id, &body.collider, &statuses)) {
+            if let Some(effect) = statuses.burning() {
+                burning.push(Burning {
+                    target: Some(DamageTarget::Actor(id)),
+                    collider: collider.clone(),
+                    config: self.config.fire.clone(),
+                    source: effect.source,
+                });
+            }
+        }
+        for (id, collider, statuses) in query!(self.blocks, (id, &collider, &statuses)) {
+            if let Some(effect) = statuses.burning() {
+                burning.push(Burning {
+                    target: Some(DamageTarget::Block(id)),
+                    collider: collider.clone(),
+                    config: self.config.fire.clone(),
+                    source: effect.source,
+                });
+            }
+        }
+        for (collider, config, source) in query!(self.fire, (&collider, &config, &source)) {
+            burning.push(Burning {
+                target: None,
+                collider: collider.clone(),
+                config: config.clone(),
+                source: Some(*source),
+            });
+        }
+
+        // How fast every neighbour heats up, and the fire it catches
+        let actors_hash = self.actors_hash();
+        let blocks_hash = self.blocks_hash();
+        let mut heating: HashMap<DamageTarget, (R32, FireConfig, Option<DamageSource>)> =
+            HashMap::new();
+        for fire in &burning {
+            if fire.config.spread_time <= Time::ZERO {
+                continue;
+            }
+            let rate = R32::ONE / fire.config.spread_time;
+            let probe = Collider::new(
+                fire.collider.position,
+                Shape::Circle {
+                    radius: fire.collider.shape.bounding_radius() + fire.config.spread_distance,
+                },
+            );
+
+            // The fire spreads faster downwind
+            let wind = self.level.flow(fire.collider.position, self.time);
+            let mut heat = |target: DamageTarget, position: Position| {
+                if Some(target) == fire.target {
+                    return;
+                }
+                let dir = fire
+                    .collider
+                    .position
+                    .delta_to(position)
+                    .normalize_or_zero();
+                let bias =
+                    (R32::ONE + vec2::dot(wind, dir) * self.config.forces.fire).max(R32::ZERO);
+                let rate = rate * bias;
+                let entry =
+                    heating
+                        .entry(target)
+                        .or_insert((R32::ZERO, fire.config.clone(), fire.source));
+                if rate > entry.0 {
+                    *entry = (rate, fire.config.clone(), fire.source);
+                }
+            };
+
+            // Actors standing in the fire on the ground catch it in `fire` right away
+            if fire.target.is_some() {
+                for id in actors_hash.query(&probe) {
+                    let collider = get!(self.actors, id, (&body.collider)).unwrap();
+                    if probe.check(&collider.clone()) {
+                        heat(DamageTarget::Actor(id), *collider.position);
+                    }
+                }
+            }
+            for id in blocks_hash.query(&probe) {
+                let collider = get!(self.blocks, id, (&collider)).unwrap();
+                if probe.check(&collider.clone()) {
+                    heat(DamageTarget::Block(id), *collider.position);
+                }
+            }
+        }
+
+        let mut ignited: Vec<(DamageTarget, StatusEffect)> = Vec::new();
+        let mut heat_up =
+            |target: DamageTarget, statuses: &mut StatusEffects, flammability: R32| {
+                let heating = heating.get(&target).filter(|_| {
+                    flammability > R32::ZERO
+                        && statuses.burning().is_none()
+                        && !statuses.has(StatusKind::BurntOut)
+                });
+                let Some((rate, config, source)) = heating else {
+                    // Cools down as soon as the contact is lost
+                    statuses.heat = R32::ZERO;
+                    return;
+                };
+                statuses.heat += *rate * flammability * delta_time;
+                if statuses.heat >= R32::ONE {
+                    statuses.heat = R32::ZERO;
+                    ignited.push((target, config.burning(*source)));
+                }
+            };
+
+        for (id, statuses) in query!(self.actors, (id, &mut statuses)) {
+            heat_up(DamageTarget::Actor(id), statuses, R32::ONE);
+        }
+        for (id, statuses, flammability, health) in
+            query!(self.blocks, (id, &mut statuses, &flammability, &health))
+        {
+            // Indestructible blocks do not burn
+            let flammability = if health.is_some() {
+                *flammability
+            } else {
+                R32::ZERO
+            };
+            heat_up(DamageTarget::Block(id), statuses, flammability);
+        }
+
+        for (target, effect) in ignited {
+            self.apply_status(target, effect);
+        }
+    }
+}
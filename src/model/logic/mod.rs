@@ -1,18 +1,28 @@
 mod action;
 mod actors;
+mod boss;
 mod collisions;
+mod damage;
 mod effects;
+mod fire;
 mod movement;
+mod navigation;
+mod oil;
 mod particles;
 mod player;
 mod projectiles;
+mod status;
+mod steering;
 mod waves;
 mod weapons;
 
 use super::*;
 
 impl Model {
+    /// Simulate a single tick.
     pub fn update(&mut self, delta_time: Time) -> Vec<GameEvent> {
+        self.store_prev_positions();
+
         self.time += delta_time;
         if self.actors.health.get(self.player.actor).is_some() {
             self.time_alive = self.time;
@@ -22,8 +32,9 @@ impl Model {
         self.update_gas(delta_time);
         self.update_fire(delta_time);
         self.update_explosions(delta_time);
-        self.update_on_fire(delta_time);
+        self.update_statuses(delta_time);
         self.update_waves(delta_time);
+        self.update_boss(delta_time);
 
         self.actors_ai(delta_time);
         self.control_player(delta_time);
@@ -68,8 +79,6 @@ impl Model {
     }
 
     fn check_deaths(&mut self, _delta_time: Time) {
-        let mut rng = thread_rng();
-
         // Actors
 
         let mut dead_actors: Vec<Id> = query!(self.actors, (id, &health))
@@ -80,16 +89,31 @@ impl Model {
         // let mut to_be_spawned: Vec<Projectile> = Vec::new();
         while let Some(id) = dead_actors.pop() {
             let actor = self.actors.remove(id).unwrap();
+            if actor.fraction == Fraction::Enemy {
+                self.kills += 1;
+                self.game_events.push(GameEvent::EnemyKilled {
+                    kind: actor.kind.clone(),
+                    position: actor.body.collider.position,
+                    source: actor.last_hit,
+                });
+            }
 
             // TODO: drop gasoline tank
             self.player.gasoline.change(r32(20.0));
 
+            // Credit the chain reaction to the killer
+            let source = actor.last_hit.map_or(
+                DamageSource::actor(id, actor.fraction, DamageMechanic::Explosion),
+                |hit| hit.with_mechanic(DamageMechanic::Explosion),
+            );
+
             // Explode
             if let Some(config) = self.config.death_explosion.clone() {
                 self.queued_effects.push_back(QueuedEffect {
                     effect: Effect::Explosion {
                         position: actor.body.collider.position,
                         config,
+                        source,
                     },
                 });
 
@@ -112,39 +136,39 @@ impl Model {
                 // }
             }
 
-            if let ActorKind::BossBody = actor.kind {
-                dead_actors.extend(
-                    query!(self.actors, (id, &kind))
-                        .filter(|(_, kind)| matches!(kind, ActorKind::BossFoot { .. }))
-                        .map(|(id, _)| id),
-                );
-                let gas_config = &self.config.player.barrel_state.gasoline;
-                self.gasoline.insert(Gasoline {
-                    collider: Collider::new(
-                        Position::zero(self.config.world_size),
-                        Shape::Circle { radius: r32(10.0) },
-                    ),
-                    lifetime: Lifetime::new_max(gas_config.lifetime),
-                    ignite_timer: gas_config.ignite_timer,
-                    fire_radius: r32(50.0),
-                    explosion: gas_config.explosion.clone(),
-                    fire: gas_config.fire.clone(),
-                });
-                self.queued_effects.push_back(QueuedEffect {
-                    effect: Effect::Explosion {
-                        position: Position::zero(self.config.world_size),
-                        config: ExplosionConfig {
-                            radius: r32(100.0),
-                            knockback: r32(200.0),
-                            damage: r32(0.0),
-                            ignite_gasoline: true,
-                            ignite: None,
+            if let Some(ActorAI::BossPart(part)) = &actor.ai {
+                if part.core {
+                    // Killing the core kills the whole boss
+                    let parts: Vec<Id> = query!(self.actors, (id, &ai.Get.Some))
+                        .filter(|(_, ai)| matches!(ai, ActorAI::BossPart(_)))
+                        .map(|(id, _)| id)
+                        .filter(|id| !dead_actors.contains(id))
+                        .collect();
+                    dead_actors.extend(parts);
+                }
+                if let Some(spill) = &part.death_spill {
+                    self.gasoline.spill(
+                        actor.body.collider.position,
+                        spill.radius,
+                        spill.amount,
+                        source.with_mechanic(DamageMechanic::Fire),
+                    );
+                }
+                if let Some(config) = &part.death_explosion {
+                    self.queued_effects.push_back(QueuedEffect {
+                        effect: Effect::Explosion {
+                            position: actor.body.collider.position,
+                            config: config.clone(),
+                            source,
                         },
-                    },
-                });
+                    });
+                }
             }
 
-            if rng.gen_bool(self.config.death_drop_heal_chance.as_f32().into()) {
+            if self
+                .rng
+                .gen_bool(self.config.death_drop_heal_chance.as_f32().into())
+            {
                 let config = &self.config.pickups;
                 self.pickups.insert(PickUp {
                     body: Body::new(
@@ -176,26 +200,46 @@ impl Model {
             .collect();
         for id in dead_blocks {
             let block = self.blocks.remove(id).unwrap();
+            self.flow_field.invalidate_obstacles();
+            self.game_events.push(GameEvent::BlockDestroyed {
+                kind: block.kind,
+                position: block.collider.position,
+            });
+            if let Some(burning) = block.statuses.burning() {
+                // Burnt down blocks leave the fire behind
+                let config = self.config.fire.clone();
+                self.fire.insert(Fire {
+                    collider: Collider::new(
+                        block.collider.position,
+                        Shape::Circle {
+                            radius: block.collider.shape.bounding_radius(),
+                        },
+                    ),
+                    lifetime: Lifetime::new_max(config.lifetime),
+                    config,
+                    source: burning
+                        .source
+                        .unwrap_or(DamageSource::environment(DamageMechanic::Fire)),
+                });
+            }
             if let BlockKind::Barrel = block.kind {
                 if let Some(config) = block.explosion {
-                    let gas_config = &self.config.player.barrel_state.gasoline;
-                    self.gasoline.insert(Gasoline {
-                        collider: Collider::new(
-                            block.collider.position,
-                            Shape::Circle {
-                                radius: config.radius / r32(3.0),
-                            },
-                        ),
-                        lifetime: Lifetime::new_max(gas_config.lifetime),
-                        ignite_timer: gas_config.ignite_timer,
-                        fire_radius: config.radius / r32(3.0),
-                        explosion: gas_config.explosion.clone(),
-                        fire: gas_config.fire.clone(),
-                    });
+                    // Credit the chain reaction to whoever destroyed the barrel
+                    let source = block.last_hit.map_or(
+                        DamageSource::environment(DamageMechanic::Explosion),
+                        |hit| hit.with_mechanic(DamageMechanic::Explosion),
+                    );
+                    self.gasoline.spill(
+                        block.collider.position,
+                        config.radius / r32(3.0),
+                        self.config.player.barrel_state.gasoline.amount,
+                        source.with_mechanic(DamageMechanic::Fire),
+                    );
                     self.queued_effects.push_back(QueuedEffect {
                         effect: Effect::Explosion {
                             position: block.collider.position,
                             config,
+                            source,
                         },
                     });
                 }
@@ -204,24 +248,11 @@ impl Model {
         }
     }
 
-    fn ignite_gasoline(&mut self, gas_id: Id) {
-        if let Some(gas) = self.gasoline.remove(gas_id) {
-            self.queued_effects.push_back(QueuedEffect {
-                effect: Effect::Explosion {
-                    position: gas.collider.position,
-                    config: gas.explosion,
-                },
-            });
-            self.fire.insert(Fire {
-                collider: Collider::new(
-                    gas.collider.position,
-                    Shape::Circle {
-                        radius: gas.fire_radius,
-                    },
-                ),
-                lifetime: Lifetime::new_max(r32(5.0)),
-                config: gas.fire,
-            });
+    /// Set the gasoline within the radius on fire.
+    fn ignite_gasoline(&mut self, position: Position, radius: Coord, igniter: DamageSource) {
+        if self.gasoline.ignite(position, radius, igniter) {
+            self.game_events
+                .push(GameEvent::GasolineIgnited { position });
         }
     }
 
@@ -267,33 +298,27 @@ impl Model {
 
         // Screen shake
         self.screen_shake
-            .apply_to_camera(&mut self.camera, delta_time);
+            .apply_to_camera(&mut self.camera, &mut self.rng, delta_time);
         self.screen_shake.update(delta_time);
     }
 
     fn update_gas(&mut self, delta_time: Time) {
-        struct GasRef<'a> {
-            id: Id,
-            lifetime: &'a mut Lifetime,
-        }
-
-        let mut expired: Vec<Id> = Vec::new();
-        for gas in query!(
-            self.gasoline,
-            GasRef {
-                id,
-                lifetime: &mut lifetime
-            }
-        ) {
-            gas.lifetime.change(-delta_time);
-            if gas.lifetime.is_min() {
-                expired.push(gas.id);
+        let config = &self.config.player.barrel_state.gasoline;
+        let flow = |position: Position| self.level.flow(position, self.time);
+        for cell in self
+            .gasoline
+            .update(delta_time, &config.fire, &self.config.forces, flow)
+        {
+            if cell.explodes {
+                self.queued_effects.push_back(QueuedEffect {
+                    effect: Effect::Explosion {
+                        position: cell.position,
+                        config: config.explosion.clone(),
+                        source: cell.source.with_mechanic(DamageMechanic::Explosion),
+                    },
+                });
             }
         }
-
-        for id in expired {
-            self.gasoline.remove(id);
-        }
     }
 
     fn update_fire(&mut self, delta_time: Time) {
@@ -321,88 +346,6 @@ impl Model {
         }
     }
 
-    fn update_on_fire(&mut self, delta_time: Time) {
-        struct ActorRef<'a> {
-            position: &'a Position,
-            health: &'a mut Health,
-            on_fire: &'a mut Option<OnFire>,
-            stats: &'a Stats,
-        }
-
-        for actor in query!(
-            self.actors,
-            ActorRef {
-                position: &body.collider.position,
-                health: &mut health,
-                on_fire: &mut on_fire,
-                stats,
-            }
-        ) {
-            if let Some(on_fire) = actor.on_fire {
-                actor.health.change(
-                    -on_fire.damage_per_second * actor.stats.vulnerability.fire * delta_time,
-                );
-
-                self.queued_effects.push_back(QueuedEffect {
-                    effect: Effect::Particles {
-                        position: *actor.position,
-                        position_radius: r32(2.0),
-                        velocity: vec2::UNIT_Y,
-                        size: r32(0.2),
-                        lifetime: r32(1.0),
-                        intensity: on_fire.damage_per_second,
-                        kind: ParticleKind::Fire,
-                    },
-                });
-
-                on_fire.duration -= delta_time;
-                if on_fire.duration <= Time::ZERO {
-                    *actor.on_fire = None;
-                }
-            }
-        }
-
-        struct BlockRef<'a> {
-            position: &'a Position,
-            health: &'a mut Health,
-            on_fire: &'a mut Option<OnFire>,
-            vulnerability: &'a VulnerabilityStats,
-        }
-
-        for block in query!(
-            self.blocks,
-            BlockRef {
-                position: &collider.position,
-                health: &mut health.Get.Some,
-                on_fire: &mut on_fire,
-                vulnerability,
-            }
-        ) {
-            if let Some(on_fire) = block.on_fire {
-                block
-                    .health
-                    .change(-on_fire.damage_per_second * block.vulnerability.fire * delta_time);
-
-                self.queued_effects.push_back(QueuedEffect {
-                    effect: Effect::Particles {
-                        position: *block.position,
-                        position_radius: r32(1.0),
-                        velocity: vec2::UNIT_Y,
-                        size: r32(0.1),
-                        lifetime: r32(1.0),
-                        intensity: on_fire.damage_per_second,
-                        kind: ParticleKind::Fire,
-                    },
-                });
-
-                on_fire.duration -= delta_time;
-                if on_fire.duration <= Time::ZERO {
-                    *block.on_fire = None;
-                }
-            }
-        }
-    }
-
     fn update_pickups(&mut self, delta_time: Time) {
         struct PickupRef<'a> {
             id: Id,
@@ -479,13 +422,3 @@ impl Model {
         (Coord::ONE / (distance.max(Coord::ONE) / r32(20.0)).sqr()).min(Coord::ONE)
     }
 }
-
-fn update_on_fire(status: Option<OnFire>, update: OnFire) -> OnFire {
-    let mut on_fire = status.unwrap_or(OnFire {
-        duration: Time::ZERO,
-        damage_per_second: Hp::ZERO,
-    });
-    on_fire.duration = on_fire.duration.max(update.duration);
-    on_fire.damage_per_second = on_fire.damage_per_second.max(update.damage_per_second);
-    on_fire
-}
@@ -1,6 +1,17 @@
 use super::*;
 
 impl Model {
+    /// Remember the positions at the start of the tick for render interpolation.
+    pub(super) fn store_prev_positions(&mut self) {
+        for (prev_position, &position) in query!(
+            [self.actors, self.projectiles, self.pickups],
+            (&mut body.prev_position, &body.collider.position)
+        ) {
+            *prev_position = position;
+        }
+        self.camera.prev_center = self.camera.center;
+    }
+
     /// System that moves all bodies in the world according to their velocity.
     pub(super) fn movement(&mut self, delta_time: Time) {
         for (position, &velocity) in query!(
@@ -9,5 +20,15 @@ impl Model {
         ) {
             position.shift(velocity * delta_time);
         }
+
+        // Force fields carry the bodies along
+        let forces = &self.config.forces;
+        let flow = |position: Position| self.level.flow(position, self.time);
+        for position in query!(self.actors, (&mut body.collider.position)) {
+            position.shift(flow(*position) * forces.actors * delta_time);
+        }
+        for position in query!(self.projectiles, (&mut body.collider.position)) {
+            position.shift(flow(*position) * forces.projectiles * delta_time);
+        }
     }
 }
@@ -0,0 +1,15 @@
+use super::*;
+
+impl Model {
+    /// Rebuild the obstacles if the blocks have changed, and retarget the paths to the player.
+    pub(super) fn update_flow_field(&mut self, target: Position) {
+        if self.flow_field.obstacles_outdated() {
+            let colliders: Vec<Collider> = query!(self.blocks, (id, &collider))
+                .map(|(_, collider)| collider.clone())
+                .collect();
+            self.flow_field
+                .set_obstacles(&colliders, self.config.pathfinding.obstacle_margin);
+        }
+        self.flow_field.update_target(target);
+    }
+}
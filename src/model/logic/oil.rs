@@ -0,0 +1,145 @@
+use super::*;
+
+impl Model {
+    /// Soak the actors walking through gasoline, and let the soaked actors drip and spread the fire.
+    pub(super) fn update_oil(&mut self, delta_time: Time) {
+        let Some(config) = self.config.player.barrel_state.gasoline.soak.clone() else {
+            return;
+        };
+        self.soak_actors(&config);
+        self.drip_oil(&config, delta_time);
+        self.spread_oil_fire(&config);
+    }
+
+    /// Set the target on fire if it is soaked in oil.
+    pub(super) fn ignite_soaked(&mut self, target: DamageTarget, source: DamageSource) {
+        let DamageTarget::Actor(id) = target else {
+            return;
+        };
+        let Some(config) = &self.config.player.barrel_state.gasoline.soak else {
+            return;
+        };
+        let soaked = self
+            .actors
+            .statuses
+            .get(id)
+            .is_some_and(|statuses| statuses.soaked().is_some());
+        if soaked {
+            let effect = config
+                .fire
+                .burning(Some(source.with_mechanic(DamageMechanic::Fire)));
+            self.apply_status(target, effect);
+        }
+    }
+
+    /// Actors in gasoline get soaked, or set it on fire if they are burning.
+    fn soak_actors(&mut self, config: &SoakConfig) {
+        struct ActorRef<'a> {
+            id: Id,
+            collider: ColliderRef<'a>,
+            statuses: &'a StatusEffects,
+        }
+
+        let mut soaked: Vec<(Id, DamageSource)> = Vec::new();
+        let mut to_ignite: Vec<(Position, Coord, DamageSource)> = Vec::new();
+        for actor in query!(
+            self.actors,
+            ActorRef {
+                id,
+                collider: &body.collider,
+                statuses,
+            }
+        ) {
+            let position = *actor.collider.position;
+            if let Some(burning) = actor.statuses.burning() {
+                to_ignite.push((
+                    position,
+                    actor.collider.shape.bounding_radius(),
+                    burning
+                        .source
+                        .unwrap_or(DamageSource::environment(DamageMechanic::Fire)),
+                ));
+                continue;
+            }
+            if self.gasoline.density(position) >= config.min_density
+                && self.gasoline.soaks(position)
+            {
+                if let Some(source) = self.gasoline.source(position) {
+                    soaked.push((actor.id, source));
+                }
+            }
+        }
+
+        for (actor_id, source) in soaked {
+            self.apply_status(DamageTarget::Actor(actor_id), config.soaked(source));
+        }
+        for (position, radius, igniter) in to_ignite {
+            self.ignite_gasoline(position, radius, igniter);
+        }
+    }
+
+    /// Soaked actors leave a trail of gasoline behind.
+    fn drip_oil(&mut self, config: &SoakConfig, delta_time: Time) {
+        let chance = (config.drip.rate * delta_time).as_f32().clamp(0.0, 1.0);
+        let mut drops: Vec<(Position, DamageSource)> = Vec::new();
+        for (position, statuses) in query!(self.actors, (&body.collider.position, &statuses)) {
+            let Some(soaked) = statuses.soaked() else {
+                continue;
+            };
+            if self.rng.gen_bool(chance.into()) {
+                drops.push((
+                    *position,
+                    soaked
+                        .source
+                        .unwrap_or(DamageSource::environment(DamageMechanic::Fire)),
+                ));
+            }
+        }
+
+        for (position, source) in drops {
+            self.gasoline
+                .drip(position, config.drip.radius, config.drip.amount, source);
+        }
+    }
+
+    /// Burning soaked actors set on fire whoever they touch.
+    fn spread_oil_fire(&mut self, config: &SoakConfig) {
+        struct ActorRef<'a> {
+            id: Id,
+            collider: ColliderRef<'a>,
+            statuses: &'a StatusEffects,
+        }
+
+        let actors_hash = self.actors_hash();
+        let mut ignited: Vec<(Id, Option<DamageSource>)> = Vec::new();
+        for actor in query!(
+            self.actors,
+            ActorRef {
+                id,
+                collider: &body.collider,
+                statuses,
+            }
+        ) {
+            if actor.statuses.soaked().is_none() {
+                continue;
+            }
+            let Some(burning) = actor.statuses.burning() else {
+                continue;
+            };
+            let collider = actor.collider.clone();
+            for other_id in actors_hash.query(&collider) {
+                if other_id == actor.id {
+                    continue;
+                }
+                let other = get!(self.actors, other_id, (&body.collider)).unwrap();
+                if collider.check(&other.clone()) {
+                    ignited.push((other_id, burning.source));
+                }
+            }
+        }
+
+        for (actor_id, source) in ignited {
+            self.apply_status(DamageTarget::Actor(actor_id), config.fire.burning(source));
+        }
+    }
+}
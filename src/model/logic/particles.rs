@@ -10,6 +10,7 @@ impl Model {
             kind: &'a ParticleKind,
         }
 
+        let response = self.config.forces.particles;
         let mut to_remove: Vec<Id> = Vec::new();
         for particle in query!(
             self.particles,
@@ -37,11 +38,20 @@ impl Model {
                     let dir = angle.unit_vec().rotate_90();
                     *particle.velocity = dir * particle.velocity.len();
                 }
-                ParticleKind::Damage | ParticleKind::Heal | ParticleKind::Projectile => {}
+                ParticleKind::Damage
+                | ParticleKind::Heal
+                | ParticleKind::Projectile
+                | ParticleKind::Poison
+                | ParticleKind::Frost
+                | ParticleKind::Oil
+                | ParticleKind::Debris => {}
             }
 
             // Move
-            particle.position.shift(*particle.velocity * delta_time);
+            let flow = self.level.flow(*particle.position, self.time) * response;
+            particle
+                .position
+                .shift((*particle.velocity + flow) * delta_time);
         }
 
         for id in to_remove {
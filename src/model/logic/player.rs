@@ -108,14 +108,12 @@ impl Model {
                     break;
                 }
                 self.player.gasoline.change(-config.cost);
-                self.gasoline.insert(Gasoline {
-                    collider: Collider::new(position, config.shape),
-                    lifetime: Lifetime::new_max(config.lifetime),
-                    ignite_timer: config.ignite_timer,
-                    fire_radius: config.fire_radius,
-                    explosion: config.explosion.clone(),
-                    fire: config.fire.clone(),
-                });
+                self.gasoline.spill(
+                    position,
+                    config.radius,
+                    config.amount,
+                    DamageSource::actor(self.player.actor, Fraction::Player, DamageMechanic::Fire),
+                );
             }
         } else {
             last_gas = *player.body.collider.position;
@@ -1,21 +1,33 @@
 use super::*;
 
+use std::f32::consts::PI;
+
 impl Model {
     pub(super) fn control_projectiles(&mut self, delta_time: Time) {
         struct ProjRef<'a> {
             id: Id,
             lifetime: &'a mut Lifetime,
             fraction: &'a Fraction,
+            owner: &'a Option<Id>,
             position: &'a Position,
+            shape: &'a Shape,
             rotation: &'a mut Angle<R32>,
             velocity: &'a mut vec2<Coord>,
             target_pos: &'a Option<Position>,
             ai: &'a mut ProjectileAI,
+            speed: &'a Coord,
+            speed_curve: &'a Vec<R32>,
+        }
+
+        struct ActorRef<'a> {
+            fraction: &'a Fraction,
+            collider: ColliderRef<'a>,
         }
 
         let mut grounded_projs: Vec<Id> = Vec::new();
         let mut kill_projs: Vec<Id> = Vec::new();
-        let mut to_be_spawned: Vec<Projectile> = Vec::new();
+        let mut to_be_fired: Vec<QueuedShot> = Vec::new();
+        let actors_hash = self.actors_hash();
 
         for proj in query!(
             self.projectiles,
@@ -23,22 +35,33 @@ impl Model {
                 id,
                 lifetime: &mut lifetime,
                 fraction,
+                owner,
                 position: &body.collider.position,
+                shape: &body.collider.shape,
                 rotation: &mut body.collider.rotation,
                 velocity: &mut body.velocity,
                 target_pos,
                 ai: &mut ai,
+                speed,
+                speed_curve,
             }
         ) {
             // Update lifetime
             proj.lifetime.change(-delta_time);
             if proj.lifetime.is_min() {
-                kill_projs.push(proj.id);
+                if proj.target_pos.is_some() {
+                    // Falls short of the target
+                    grounded_projs.push(proj.id);
+                } else {
+                    kill_projs.push(proj.id);
+                }
                 continue;
             }
 
-            // Update rotation
-            *proj.rotation = proj.velocity.arg();
+            // Update rotation, keeping the heading while stopped
+            if *proj.velocity != vec2::ZERO {
+                *proj.rotation = proj.velocity.arg();
+            }
 
             if let Some(target_pos) = *proj.target_pos {
                 // Target position is specified, so the projectile should stop at the target
@@ -46,9 +69,17 @@ impl Model {
                 if vec2::dot(target_dir, *proj.velocity) < Coord::ZERO {
                     // The projectile is travelling away from the target
                     grounded_projs.push(proj.id);
+                    continue;
                 }
             }
 
+            // Speed curve
+            let progress = R32::ONE - proj.lifetime.get_ratio();
+            if let Some(multiplier) = speed_multiplier(proj.speed_curve, progress) {
+                // Scale along the heading, since the velocity is lost when the curve reaches zero
+                *proj.velocity = proj.rotation.unit_vec() * *proj.speed * multiplier;
+            }
+
             match proj.ai {
                 ProjectileAI::Straight => {}
                 ProjectileAI::ConstantTurn { degrees_per_second } => {
@@ -56,9 +87,65 @@ impl Model {
                     let angle = Angle::from_degrees(*degrees_per_second * delta_time);
                     *proj.velocity = proj.velocity.rotate(angle);
                 }
+                ProjectileAI::Homing {
+                    degrees_per_second,
+                    cone_degrees,
+                    range,
+                } => {
+                    // The nearest hostile actor in the cone
+                    let probe = Collider::new(*proj.position, Shape::Circle { radius: *range });
+                    let half_cone = cone_degrees.as_f32().to_radians() / 2.0;
+                    let target = actors_hash
+                        .query(&probe)
+                        .into_iter()
+                        .filter_map(|id| {
+                            let actor = get!(
+                                self.actors,
+                                id,
+                                ActorRef {
+                                    fraction,
+                                    collider: &body.collider
+                                }
+                            )?;
+                            if actor.fraction == proj.fraction {
+                                return None;
+                            }
+                            let delta = proj.position.delta_to(*actor.collider.position);
+                            let distance = delta.len();
+                            let angle = angle_between(*proj.velocity, delta);
+                            (distance <= *range && angle <= half_cone).then_some((delta, distance))
+                        })
+                        .min_by_key(|&(_, distance)| distance);
+                    if let Some((delta, _)) = target {
+                        let max_angle = *degrees_per_second * delta_time;
+                        *proj.velocity = turn_towards(*proj.velocity, delta, max_angle);
+                    }
+                }
+                ProjectileAI::Boomerang {
+                    return_after,
+                    degrees_per_second,
+                } => {
+                    let elapsed = proj.lifetime.max() - proj.lifetime.value();
+                    let owner = proj
+                        .owner
+                        .and_then(|owner| get!(self.actors, owner, (&body.collider)));
+                    if let Some(owner) = owner.filter(|_| elapsed >= *return_after) {
+                        let delta = proj.position.delta_to(*owner.position);
+                        let catch_distance =
+                            owner.shape.bounding_radius() + proj.shape.bounding_radius();
+                        if delta.len() <= catch_distance {
+                            // Caught by the shooter
+                            kill_projs.push(proj.id);
+                            continue;
+                        }
+                        let max_angle = *degrees_per_second * delta_time;
+                        *proj.velocity = turn_towards(*proj.velocity, delta, max_angle);
+                    }
+                }
                 ProjectileAI::CircleBomb {
                     explosive_type,
                     delay,
+                    pattern,
                 } => {
                     // Until the delay is over, the projectile flies straight
                     if *delay >= Time::ZERO {
@@ -66,16 +153,17 @@ impl Model {
                     } else {
                         // Explode!
                         kill_projs.push(proj.id);
-
-                        // Create a circle of projectiles
-                        for i in 0..18 {
-                            to_be_spawned.push(Projectile::new(
-                                *proj.position,
-                                Angle::from_degrees(r32(i as f32 * 20.0)),
-                                *proj.fraction,
-                                *explosive_type.clone(),
-                            ));
-                        }
+                        to_be_fired.push(QueuedShot {
+                            delay: Time::ZERO,
+                            position: *proj.position,
+                            aim: Angle::ZERO,
+                            range: None,
+                            fraction: *proj.fraction,
+                            owner: *proj.owner,
+                            follow_owner: false,
+                            pattern: pattern.clone(),
+                            projectile: (**explosive_type).clone(),
+                        });
                     }
                 }
             }
@@ -86,24 +174,85 @@ impl Model {
             self.projectiles.remove(id);
         }
 
-        for proj in to_be_spawned {
-            self.projectiles.insert(proj);
+        for shot in to_be_fired {
+            self.fire_pattern(shot);
         }
 
         // Every grounded projectile ignites gasoline
-        let mut ignite: Vec<Id> = Vec::new();
         for proj_id in grounded_projs {
             let proj = self.projectiles.remove(proj_id).unwrap();
-            for (gas_id, gas_collider) in query!(self.gasoline, (id, &collider)) {
-                if proj.body.collider.check(&gas_collider.clone()) {
-                    // Ignite gasoline
-                    ignite.push(gas_id);
-                }
+            let position = proj.body.collider.position;
+            let source = proj.damage_source();
+            self.ignite_gasoline(position, proj.body.collider.shape.bounding_radius(), source);
+            if let Some(thrown) = proj.thrown {
+                self.land_thrown(position, thrown.config, source);
             }
         }
+    }
 
-        for id in ignite {
-            self.ignite_gasoline(id);
+    /// Thrown projectile lands at the position.
+    fn land_thrown(&mut self, position: Position, config: ThrowConfig, source: DamageSource) {
+        if let Some(spill) = config.spill {
+            self.gasoline.spill(
+                position,
+                spill.radius,
+                spill.amount,
+                source.with_mechanic(DamageMechanic::Fire),
+            );
+        }
+        if let Some(radius) = config.ignite_radius {
+            self.ignite_gasoline(position, radius, source.with_mechanic(DamageMechanic::Fire));
+        }
+        if let Some(puddle) = config.fire {
+            self.fire.insert(Fire {
+                collider: Collider::new(
+                    position,
+                    Shape::Circle {
+                        radius: puddle.radius,
+                    },
+                ),
+                lifetime: Lifetime::new_max(puddle.fire.lifetime),
+                config: puddle.fire,
+                source: source.with_mechanic(DamageMechanic::Fire),
+            });
+        }
+        if let Some(config) = config.explosion {
+            self.queued_effects.push_back(QueuedEffect {
+                effect: Effect::Explosion {
+                    position,
+                    config,
+                    source: source.with_mechanic(DamageMechanic::Explosion),
+                },
+            });
         }
     }
 }
+
+/// Speed multiplier of the curve, `progress` going from 0 to 1 over the lifetime.
+fn speed_multiplier(curve: &[R32], progress: R32) -> Option<R32> {
+    match curve {
+        [] => None,
+        [multiplier] => Some(*multiplier),
+        _ => {
+            let segments = curve.len() - 1;
+            let t = progress.clamp(R32::ZERO, R32::ONE) * r32(segments as f32);
+            let i = (t.as_f32().floor() as usize).min(segments - 1);
+            let t = t - r32(i as f32);
+            Some(curve[i] + (curve[i + 1] - curve[i]) * t)
+        }
+    }
+}
+
+/// Angle between the two directions in radians, from 0 to PI.
+fn angle_between(a: vec2<Coord>, b: vec2<Coord>) -> f32 {
+    let diff = (b.arg().as_radians() - a.arg().as_radians()).as_f32();
+    ((diff + PI).rem_euclid(2.0 * PI) - PI).abs()
+}
+
+/// Rotate the velocity toward the direction by at most `max_degrees`.
+fn turn_towards(velocity: vec2<Coord>, direction: vec2<Coord>, max_degrees: R32) -> vec2<Coord> {
+    let diff = (direction.arg().as_radians() - velocity.arg().as_radians()).as_f32();
+    let diff = (diff + PI).rem_euclid(2.0 * PI) - PI;
+    let max = max_degrees.as_f32().to_radians();
+    velocity.rotate(Angle::from_radians(r32(diff.clamp(-max, max))))
+}
@@ -0,0 +1,171 @@
+use super::*;
+
+impl Model {
+    /// Apply the status effects on behalf of the source.
+    pub(super) fn apply_statuses(
+        &mut self,
+        target: DamageTarget,
+        statuses: &[StatusEffect],
+        source: DamageSource,
+    ) {
+        for &effect in statuses {
+            self.apply_status(
+                target,
+                StatusEffect {
+                    source: Some(source.with_mechanic(effect.kind.mechanic())),
+                    ..effect
+                },
+            );
+        }
+    }
+
+    /// Apply the status effect to the target, unless it is immune.
+    pub(super) fn apply_status(&mut self, target: DamageTarget, effect: StatusEffect) {
+        // Immune to the damage means immune to the effect
+        let immune = |vulnerability: &VulnerabilityStats| match effect.kind {
+            // Oil only matters to things that can burn
+            StatusKind::OilSoaked { .. } => vulnerability.fire <= R32::ZERO,
+            kind => kind
+                .damage_per_second()
+                .is_some_and(|(_, damage_type)| vulnerability.get(damage_type) <= R32::ZERO),
+        };
+
+        match target {
+            DamageTarget::Actor(id) => {
+                struct ActorRef<'a> {
+                    stats: &'a Stats,
+                    statuses: &'a mut StatusEffects,
+                }
+
+                let Some(actor) = get!(
+                    self.actors,
+                    id,
+                    ActorRef {
+                        stats,
+                        statuses: &mut statuses,
+                    }
+                ) else {
+                    return;
+                };
+                if !immune(&actor.stats.vulnerability) {
+                    actor.statuses.apply(effect);
+                }
+            }
+            DamageTarget::Block(id) => {
+                struct BlockRef<'a> {
+                    health: &'a Option<Health>,
+                    vulnerability: &'a VulnerabilityStats,
+                    statuses: &'a mut StatusEffects,
+                }
+
+                let Some(block) = get!(
+                    self.blocks,
+                    id,
+                    BlockRef {
+                        health,
+                        vulnerability,
+                        statuses: &mut statuses,
+                    }
+                ) else {
+                    return;
+                };
+                // Indestructible blocks are not affected
+                if block.health.is_some() && !immune(block.vulnerability) {
+                    block.statuses.apply(effect);
+                }
+            }
+        }
+    }
+
+    /// Deal the damage over time and count down the status effects.
+    pub(super) fn update_statuses(&mut self, delta_time: Time) {
+        struct ActorRef<'a> {
+            id: Id,
+            position: &'a Position,
+            statuses: &'a mut StatusEffects,
+        }
+
+        let mut hits: Vec<(DamageTarget, Hp, DamageType, DamageSource)> = Vec::new();
+        for actor in query!(
+            self.actors,
+            ActorRef {
+                id,
+                position: &body.collider.position,
+                statuses: &mut statuses,
+            }
+        ) {
+            for effect in actor.statuses.iter() {
+                if let Some((dps, damage_type)) = effect.kind.damage_per_second() {
+                    hits.push((
+                        DamageTarget::Actor(actor.id),
+                        dps * delta_time,
+                        damage_type,
+                        effect
+                            .source
+                            .unwrap_or(DamageSource::environment(effect.kind.mechanic())),
+                    ));
+                }
+                if let Some((kind, intensity)) = effect.kind.particles() {
+                    self.queued_effects.push_back(QueuedEffect {
+                        effect: Effect::Particles {
+                            position: *actor.position,
+                            position_radius: r32(2.0),
+                            velocity: vec2::UNIT_Y,
+                            size: r32(0.2),
+                            lifetime: r32(1.0),
+                            intensity,
+                            kind,
+                        },
+                    });
+                }
+            }
+            actor.statuses.update(delta_time);
+        }
+
+        struct BlockRef<'a> {
+            id: Id,
+            position: &'a Position,
+            statuses: &'a mut StatusEffects,
+        }
+
+        for block in query!(
+            self.blocks,
+            BlockRef {
+                id,
+                position: &collider.position,
+                statuses: &mut statuses,
+            }
+        ) {
+            for effect in block.statuses.iter() {
+                if let Some((dps, damage_type)) = effect.kind.damage_per_second() {
+                    hits.push((
+                        DamageTarget::Block(block.id),
+                        dps * delta_time,
+                        damage_type,
+                        effect
+                            .source
+                            .unwrap_or(DamageSource::environment(effect.kind.mechanic())),
+                    ));
+                }
+                if let Some((kind, intensity)) = effect.kind.particles() {
+                    self.queued_effects.push_back(QueuedEffect {
+                        effect: Effect::Particles {
+                            position: *block.position,
+                            position_radius: r32(1.0),
+                            velocity: vec2::UNIT_Y,
+                            size: r32(0.1),
+                            lifetime: r32(1.0),
+                            intensity,
+                            kind,
+                        },
+                    });
+                }
+            }
+            block.statuses.update(delta_time);
+        }
+
+        for (target, damage, damage_type, source) in hits {
+            self.damage_over_time(target, damage, damage_type, source);
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use super::*;
+
+use std::f32::consts::PI;
+
+impl Model {
+    /// Calculate the steering forces for the actors controlled by the AI,
+    /// to be added to their desired movement direction.
+    pub(super) fn steering(&self, target: Position) -> HashMap<Id, vec2<Coord>> {
+        struct ActorRef<'a> {
+            id: Id,
+            collider: ColliderRef<'a>,
+            velocity: &'a vec2<Coord>,
+            fraction: &'a Fraction,
+            steering: &'a SteeringConfig,
+            ai: &'a Option<ActorAI>,
+        }
+
+        struct OtherRef<'a> {
+            id: Id,
+            collider: ColliderRef<'a>,
+            fraction: &'a Fraction,
+        }
+
+        struct BlockRef<'a> {
+            collider: ColliderRef<'a>,
+        }
+
+        let actors_hash = self.actors_hash();
+        let blocks_hash = self.blocks_hash();
+
+        // Bearings of the actors around the target, used for flanking
+        let bearings: Vec<(Id, Fraction, Coord, f32)> = query!(
+            self.actors,
+            (id, &body.collider.position, &fraction, &ai.Get.Some)
+        )
+        .map(|(id, &position, &fraction, _)| {
+            let delta = target.delta_to(position);
+            (id, fraction, delta.len(), bearing(delta))
+        })
+        .collect();
+
+        let mut forces = HashMap::new();
+        for actor in query!(
+            self.actors,
+            ActorRef {
+                id,
+                collider: &body.collider,
+                velocity: &body.velocity,
+                fraction,
+                steering,
+                ai,
+            }
+        ) {
+            if actor.ai.is_none() {
+                continue;
+            }
+            let config = actor.steering;
+            let collider = actor.collider.clone();
+            let position = collider.position;
+            let radius = collider.shape.bounding_radius();
+            let mut force = vec2::ZERO;
+
+            // Separation
+            if config.separation > R32::ZERO {
+                let area = Collider::new(
+                    position,
+                    Shape::Circle {
+                        radius: radius + config.separation_radius,
+                    },
+                );
+                let mut push = vec2::ZERO;
+                for other_id in actors_hash.query(&area) {
+                    let other = get!(
+                        self.actors,
+                        other_id,
+                        OtherRef {
+                            id,
+                            collider: &body.collider,
+                            fraction,
+                        }
+                    )
+                    .unwrap();
+                    if other.id == actor.id || other.fraction != actor.fraction {
+                        continue;
+                    }
+                    let delta = other.collider.position.delta_to(position);
+                    let gap = delta.len() - radius - other.collider.shape.bounding_radius();
+                    if gap >= config.separation_radius {
+                        continue;
+                    }
+                    let strength = if config.separation_radius > Coord::ZERO {
+                        (Coord::ONE - gap / config.separation_radius).clamp(Coord::ZERO, Coord::ONE)
+                    } else {
+                        Coord::ONE
+                    };
+                    push += delta.normalize_or_zero() * strength;
+                }
+                force += push * config.separation;
+            }
+
+            // Obstacle avoidance
+            if config.avoidance > R32::ZERO && config.avoidance_distance > Coord::ZERO {
+                let heading = if actor.velocity.len() > Coord::ZERO {
+                    actor.velocity.normalize_or_zero()
+                } else {
+                    position.delta_to(target).normalize_or_zero()
+                };
+                let probe = Collider::new(
+                    position.shifted(heading * config.avoidance_distance),
+                    Shape::Circle { radius },
+                );
+                for block_id in blocks_hash.query(&probe) {
+                    let block = get!(self.blocks, block_id, BlockRef { collider })
+                        .unwrap()
+                        .collider
+                        .clone();
+                    if !probe.check(&block) {
+                        continue;
+                    }
+                    // Steer sideways, away from the center of the block
+                    let away = block.position.delta_to(probe.position);
+                    let side = away - heading * vec2::dot(away, heading);
+                    let side = if side.len() > Coord::ZERO {
+                        side.normalize_or_zero()
+                    } else {
+                        heading.rotate_90()
+                    };
+                    force += side * config.avoidance;
+                }
+            }
+
+            // Flanking
+            if config.flanking > R32::ZERO {
+                let delta = target.delta_to(position);
+                let distance = delta.len();
+                if distance < config.flanking_distance && distance > Coord::ZERO {
+                    let own_bearing = bearing(delta);
+                    // Spread away from the allies approaching from a similar direction
+                    let mut spread = 0.0;
+                    let mut allies = 0;
+                    for &(id, fraction, distance, other_bearing) in &bearings {
+                        if id == actor.id
+                            || fraction != *actor.fraction
+                            || distance >= config.flanking_distance
+                        {
+                            continue;
+                        }
+                        let diff = (own_bearing - other_bearing + PI).rem_euclid(2.0 * PI) - PI;
+                        if diff == 0.0 {
+                            continue;
+                        }
+                        spread += diff.signum() * (1.0 - diff.abs() / PI);
+                        allies += 1;
+                    }
+                    if allies > 0 {
+                        let spread = r32(spread / allies as f32);
+                        let tangent = delta.normalize_or_zero().rotate_90();
+                        force += tangent * spread * config.flanking;
+                    }
+                }
+            }
+
+            forces.insert(actor.id, force);
+        }
+        forces
+    }
+}
+
+/// Angle of the direction in radians.
+fn bearing(delta: vec2<Coord>) -> f32 {
+    let delta = delta.as_f32();
+    delta.y.atan2(delta.x)
+}
@@ -10,6 +10,8 @@ impl Model {
                 .infinite_wave
                 .difficulty_time_scaling;
 
+        self.check_waves_cleared();
+
         // Starting delay
         if self.wave_manager.wave_delay > Time::ZERO {
             self.wave_manager.wave_delay -= delta_time;
@@ -22,8 +24,6 @@ impl Model {
             return;
         }
 
-        let mut rng = thread_rng();
-
         if let Some(enemy_name) = self.wave_manager.current_wave.enemies.pop_front() {
             // Spawn the next enemy
             let enemy_config = self
@@ -31,7 +31,9 @@ impl Model {
                 .get(&enemy_name)
                 .unwrap_or_else(|| panic!("Enemy {:?} not found", enemy_name))
                 .clone();
-            let pos = rng.gen_circle(vec2::ZERO, self.wave_manager.config.spawn_circle_radius);
+            let pos = self
+                .rng
+                .gen_circle(vec2::ZERO, self.wave_manager.config.spawn_circle_radius);
             let pos = self.wave_manager.spawn_point.shifted(pos);
             let _enemy = self.actors.insert(Actor::new_enemy(pos, enemy_config));
             // self.wave_manager.current_enemies.push(enemy);
@@ -68,9 +70,21 @@ impl Model {
         }
 
         // No more waves found
-        if self.wave_manager.infinite_wave_number == self.waves.infinite_waves_until_boss {
-            // Spawn boss
-            self.boss_wave();
+        let boss = self
+            .waves
+            .boss
+            .as_ref()
+            .filter(|_| {
+                self.wave_manager.infinite_wave_number == self.waves.infinite_waves_until_boss
+            })
+            .map(|name| {
+                self.bosses
+                    .get(name)
+                    .unwrap_or_else(|| panic!("Boss {:?} not found", name))
+                    .clone()
+            });
+        if let Some(boss) = boss {
+            self.boss_wave(boss);
         } else {
             // Infinite wave
             let config = &self.wave_manager.config.infinite_wave;
@@ -87,7 +101,7 @@ impl Model {
                     .enemies
                     .iter()
                     .filter(|(_, config)| config.cost <= points)
-                    .choose(&mut rng)
+                    .choose(&mut self.rng)
                 else {
                     break;
                 };
@@ -100,9 +114,32 @@ impl Model {
         self.wave_manager.infinite_wave_number += 1;
     }
 
-    fn switch_wave(&mut self, wave: WaveConfig) {
-        let mut rng = thread_rng();
+    /// Once all the spawned enemies are dead, the started waves are cleared.
+    fn check_waves_cleared(&mut self) {
+        if self.wave_manager.uncleared.is_empty()
+            || !self.wave_manager.current_wave.enemies.is_empty()
+        {
+            return;
+        }
+        if query!(self.actors, (&fraction)).any(|fraction| *fraction != Fraction::Player) {
+            return;
+        }
+        for wave in std::mem::take(&mut self.wave_manager.uncleared) {
+            self.game_events.push(GameEvent::WaveCleared { wave });
+        }
+    }
+
+    fn start_wave(&mut self) {
+        self.wave_manager.wave_number += 1;
+        self.wave_manager
+            .uncleared
+            .push(self.wave_manager.wave_number);
+        self.game_events.push(GameEvent::WaveStarted {
+            wave: self.wave_manager.wave_number,
+        });
+    }
 
+    fn switch_wave(&mut self, wave: WaveConfig) {
         // Add missing barrels
         let amount = self
             .blocks
@@ -120,30 +157,29 @@ impl Model {
         };
 
         let config = &self.wave_manager.config;
-        let angle = Angle::from_degrees(r32(rng.gen_range(0.0..=360.0)));
-        let distance = rng.gen_range(config.min_spawn_distance..=config.max_spawn_distance);
+        let angle = Angle::from_degrees(r32(self.rng.gen_range(0.0..=360.0)));
+        let distance = self
+            .rng
+            .gen_range(config.min_spawn_distance..=config.max_spawn_distance);
         self.wave_manager.spawn_point = player_pos.shifted(angle.unit_vec() * distance);
 
         self.wave_manager.current_wave = wave;
-        self.wave_manager.wave_number += 1;
+        self.start_wave();
     }
 
-    fn boss_wave(&mut self) {
-        // let mut rng = thread_rng();
+    fn boss_wave(&mut self, config: BossConfig) {
+        let boss_position = Position::zero(self.config.world_size);
 
         // Explode
-        self.queued_effects.push_back(QueuedEffect {
-            effect: Effect::Explosion {
-                position: Position::zero(self.config.world_size),
-                config: ExplosionConfig {
-                    radius: r32(50.0),
-                    knockback: r32(100.0),
-                    damage: Hp::ZERO,
-                    ignite_gasoline: false,
-                    ignite: None,
+        if let Some(explosion) = config.spawn_explosion.clone() {
+            self.queued_effects.push_back(QueuedEffect {
+                effect: Effect::Explosion {
+                    position: boss_position,
+                    config: explosion,
+                    source: DamageSource::environment(DamageMechanic::Explosion),
                 },
-            },
-        });
+            });
+        }
 
         // Remove blocks near the spawn
         let to_remove: Vec<Id> = self
@@ -151,70 +187,63 @@ impl Model {
             .collider
             .position
             .iter()
-            .filter(|(_, pos)| pos.as_dir().len().as_f32() <= 50.0)
+            .filter(|(_, pos)| pos.distance(boss_position) <= config.clear_radius)
             .map(|(id, _)| id)
             .collect();
         for id in to_remove {
             self.blocks.remove(id);
         }
+        self.flow_field.invalidate_obstacles();
 
         self.wave_manager.current_wave.wait_for_deaths = true;
 
-        // Feet
-        let mut place_foot = |pos: vec2<f32>| {
-            let position = Position::from_world(pos.as_r32(), self.config.world_size);
+        for part in &config.parts {
+            let anchor = boss_position.shifted(part.offset);
+            let attacks = config
+                .attacks(&part.attacks)
+                .expect("boss shots are checked when loading");
             self.actors.insert(Actor::new_enemy(
-                position,
+                anchor,
                 EnemyConfig {
-                    body: BodyConfig {
-                        shape: Shape::Rectangle {
-                            width: r32(8.0),
-                            height: r32(5.0),
-                        },
-                        mass: r32(1e6),
-                    },
+                    body: part.body.clone(),
                     stats: Stats {
-                        contact_damage: r32(50.0),
-                        move_speed: r32(0.0),
-                        vulnerability: VulnerabilityStats { ..default() },
-                    },
-                    acceleration: r32(0.0),
-                    hp: Hp::new(500.0),
-                    ai: ActorAI::BossFoot { position },
-                    kind: ActorKind::BossFoot {
-                        leg_offset: vec2(0.0, pos.y + 7.0).as_r32(),
+                        contact_damage: part.contact_damage,
+                        move_speed: Coord::ZERO,
+                        vulnerability: part.vulnerability.clone(),
                     },
+                    acceleration: Coord::ZERO,
+                    hp: part.hp,
+                    ai: ActorAI::BossPart(BossPart {
+                        name: part.name.clone(),
+                        anchor,
+                        motion: part.motion.clone(),
+                        attacks,
+                        core: part.core,
+                        death_spill: part.death_spill.clone(),
+                        death_explosion: part.death_explosion.clone(),
+                    }),
+                    steering: default(),
+                    kind: part.kind.clone(),
                     gun: None,
-                    stops_barrel: true,
+                    weapon: None,
+                    stops_barrel: part.stops_barrel,
                 },
             ));
-        };
+        }
 
-        place_foot(vec2(-28.0, 3.0));
-        place_foot(vec2(29.0, 5.0));
-        place_foot(vec2(31.0, -7.0));
-        place_foot(vec2(-31.0, -9.0));
-
-        // Body
-        self.actors.insert(Actor::new_enemy(
-            Position::from_world(vec2(0.0, 0.0).as_r32(), self.config.world_size),
-            EnemyConfig {
-                body: BodyConfig {
-                    shape: Shape::Circle { radius: r32(5.0) },
-                    mass: r32(1e6),
-                },
-                stats: Stats {
-                    contact_damage: r32(100.0),
-                    move_speed: r32(0.0),
-                    vulnerability: VulnerabilityStats { ..default() },
-                },
-                acceleration: r32(0.0),
-                hp: Hp::new(1000.0),
-                ai: ActorAI::BossBody,
-                kind: ActorKind::BossBody,
-                gun: None,
-                stops_barrel: true,
-            },
-        ));
+        self.boss = Some(BossState {
+            position: boss_position,
+            phase: 0,
+            phase_time: Time::ZERO,
+            max_health: config
+                .parts
+                .iter()
+                .fold(Hp::ZERO, |sum, part| sum + part.hp),
+            minion_cooldown: Time::ZERO,
+            config,
+        });
+
+        self.start_wave();
+        self.game_events.push(GameEvent::BossSpawned);
     }
 }
@@ -6,50 +6,184 @@ impl Model {
         position: Position,
         aimed_towards: Position,
         fraction: Fraction,
+        owner: Option<Id>,
         config: ShotConfig,
     ) {
-        let aim_angle = (position.delta_to(aimed_towards)).arg();
-
-        let mut shoot_at = |angle: Angle<R32>| {
-            self.projectiles.insert(Projectile::new(
-                position,
-                angle,
-                fraction,
-                config.projectile.clone(),
-            ));
-        };
+        let delta = position.delta_to(aimed_towards);
+        self.fire_pattern(QueuedShot {
+            delay: Time::ZERO,
+            position,
+            aim: delta.arg(),
+            range: Some(delta.len()),
+            fraction,
+            owner,
+            follow_owner: true,
+            pattern: config.pattern,
+            projectile: config.projectile,
+        });
 
-        match config.pattern {
-            ShotPattern::Single => shoot_at(aim_angle),
+        // Play sound
+        self.game_events.push(GameEvent::PlaySound {
+            sound: Sound::Shoot,
+            volume: self.get_volume_from(position),
+        })
+    }
+
+    /// Fire the pattern right away, or queue it if it is delayed.
+    pub(super) fn fire_pattern(&mut self, shot: QueuedShot) {
+        if shot.delay > Time::ZERO {
+            self.queued_shots.push(shot);
+            return;
+        }
+
+        // Direction and speed multiplier of every bullet
+        let mut bullets: Vec<(Angle<R32>, R32)> = Vec::new();
+        match &shot.pattern {
+            ShotPattern::Single => bullets.push((shot.aim, R32::ONE)),
             ShotPattern::Multiple {
                 spread_degrees,
-                bullets,
+                bullets: count,
+            } => {
+                for i in 0..*count {
+                    let i = i as f32 / (count - 1) as f32 - 0.5;
+                    let angle = shot.aim + Angle::from_degrees(*spread_degrees * r32(i));
+                    bullets.push((angle, R32::ONE));
+                }
+            }
+            ShotPattern::Ring { bullets: count } => {
+                for i in 0..*count {
+                    let angle =
+                        shot.aim + Angle::from_degrees(r32(360.0 * i as f32 / *count as f32));
+                    bullets.push((angle, R32::ONE));
+                }
+            }
+            ShotPattern::Random {
+                spread_degrees,
+                bullets: count,
+                speed_variance,
+            } => {
+                for _ in 0..*count {
+                    let spread = r32(self.rng.gen_range(-0.5..=0.5));
+                    let speed = r32(self.rng.gen_range(-1.0..=1.0));
+                    bullets.push((
+                        shot.aim + Angle::from_degrees(*spread_degrees * spread),
+                        R32::ONE + *speed_variance * speed,
+                    ));
+                }
+            }
+            ShotPattern::Burst {
+                shots,
+                delay,
+                pattern,
+            } => {
+                for i in 0..*shots {
+                    self.fire_pattern(QueuedShot {
+                        delay: shot.delay + *delay * r32(i as f32),
+                        pattern: (**pattern).clone(),
+                        ..shot.clone()
+                    });
+                }
+            }
+            ShotPattern::Spiral {
+                shots,
+                delay,
+                step_degrees,
+                pattern,
             } => {
-                for i in 0..bullets {
-                    let i = i as f32 / (bullets - 1) as f32 - 0.5;
-                    shoot_at(aim_angle + Angle::from_degrees(spread_degrees * r32(i)));
+                for i in 0..*shots {
+                    let i = r32(i as f32);
+                    self.fire_pattern(QueuedShot {
+                        delay: shot.delay + *delay * i,
+                        aim: shot.aim + Angle::from_degrees(*step_degrees * i),
+                        pattern: (**pattern).clone(),
+                        ..shot.clone()
+                    });
+                }
+            }
+            ShotPattern::Composite { patterns } => {
+                for pattern in patterns {
+                    self.fire_pattern(QueuedShot {
+                        pattern: pattern.clone(),
+                        ..shot.clone()
+                    });
                 }
             }
         }
 
-        // Play sound
-        self.game_events.push(GameEvent::PlaySound {
-            sound: Sound::Shoot,
-            volume: self.get_volume_from(position),
-        })
+        for (angle, speed) in bullets {
+            let config = ProjectileConfig {
+                speed: shot.projectile.speed * speed,
+                ..shot.projectile.clone()
+            };
+            let range = shot.range.unwrap_or(config.speed * config.lifetime);
+            let mut projectile =
+                Projectile::new(shot.position, angle, shot.fraction, config).with_owner(shot.owner);
+            if projectile.thrown.is_some() {
+                // Thrown projectiles land at the aimed point
+                projectile =
+                    projectile.with_target(shot.position.shifted(angle.unit_vec() * range));
+            }
+            self.projectiles.insert(projectile);
+        }
+    }
+
+    /// Fire the delayed parts of the shot patterns once their time comes.
+    fn update_queued_shots(&mut self, delta_time: Time) {
+        let mut ready: Vec<QueuedShot> = Vec::new();
+        self.queued_shots.retain_mut(|shot| {
+            shot.delay -= delta_time;
+            if shot.delay > Time::ZERO {
+                return true;
+            }
+            ready.push(shot.clone());
+            false
+        });
+
+        for mut shot in ready {
+            shot.delay = Time::ZERO;
+            if let Some(owner) = shot.owner.filter(|_| shot.follow_owner) {
+                // Follow the owner
+                let Some(&position) = get!(self.actors, owner, (&body.collider.position)) else {
+                    continue;
+                };
+                shot.position = position;
+            }
+            let position = shot.position;
+            self.fire_pattern(shot);
+            self.game_events.push(GameEvent::PlaySound {
+                sound: Sound::Shoot,
+                volume: self.get_volume_from(position),
+            });
+        }
     }
 
     pub fn update_weapons(&mut self, delta_time: Time) {
         self.update_actors(delta_time);
+        self.update_queued_shots(delta_time);
     }
 
     fn update_actors(&mut self, delta_time: Time) {
         for gun in query!(self.actors, (&mut gun.Get.Some)) {
-            update_weapon(&mut gun.shot_delay, delta_time);
+            gun.update(delta_time);
         }
     }
-}
 
-fn update_weapon(shot_delay: &mut Time, delta_time: Time) {
-    *shot_delay = (*shot_delay - delta_time).max(Time::ZERO);
+    /// Holster the active weapon of the player and draw the one `offset` slots away in the loadout.
+    pub(super) fn switch_weapon(&mut self, offset: isize) {
+        let weapons = self.player.weapons.len();
+        if weapons < 2 {
+            return;
+        }
+        let Some(gun) = self.actors.gun.get_mut(self.player.actor) else {
+            return;
+        };
+        if let Some(active) = gun.take() {
+            self.player.weapons[self.player.active_weapon].gun = active;
+        }
+        let weapons = isize::try_from(weapons).unwrap();
+        let next =
+            (isize::try_from(self.player.active_weapon).unwrap() + offset).rem_euclid(weapons);
+        self.player.active_weapon = usize::try_from(next).unwrap();
+        *gun = Some(self.player.weapons[self.player.active_weapon].gun.clone());
+    }
 }
@@ -1,20 +1,29 @@
 mod action;
+mod boss;
 mod camera;
 mod components;
+mod contacts;
+mod damage;
 mod effect;
+mod gasoline;
 mod gen;
 mod logic;
+mod navigation;
 mod player;
+mod rng;
 mod shake;
+mod snapshot;
+mod spatial;
 mod waves;
 mod weapons;
 
 pub use self::{
-    action::*, camera::*, components::*, effect::*, player::*, shake::*, waves::*, weapons::*,
+    action::*, boss::*, camera::*, components::*, contacts::*, damage::*, effect::*, gasoline::*,
+    navigation::*, player::*, rng::*, shake::*, snapshot::*, spatial::*, waves::*, weapons::*,
 };
 
 use crate::{
-    assets::{config::*, theme::Theme, waves::*},
+    assets::{bosses::*, config::*, theme::Theme, waves::*},
     game::{GameEvent, Sound},
     prelude::*,
 };
@@ -32,26 +41,51 @@ pub type Health = Bounded<Hp>;
 
 pub struct Model {
     pub theme: Theme,
+    /// The seed the simulation rng was initialized with.
+    pub seed: u64,
+    /// The only source of randomness for the simulation.
+    /// Using it (instead of `thread_rng`) keeps runs reproducible.
+    pub rng: SimRng,
     pub time: Time,
     pub time_alive: Time,
+    /// Number of enemies killed this run.
+    pub kills: usize,
     pub config: Config,
     pub level: LevelConfig,
     pub waves: WavesConfig,
     pub screen_shake: ScreenShake,
     pub camera: Camera,
     pub enemies_list: HashMap<String, EnemyConfig>,
+    pub bosses: HashMap<String, BossConfig>,
+    /// Guns referenced by name by the player loadout and the enemies.
+    pub weapons: HashMap<String, GunConfig>,
     pub wave_manager: WaveManager,
+    /// The current boss fight, if any.
+    pub boss: Option<BossState>,
     pub player: Player,
     pub actors: StructOf<Arena<Actor>>,
     pub blocks: StructOf<Arena<Block>>,
     pub background_blocks: StructOf<Arena<Block>>,
     pub projectiles: StructOf<Arena<Projectile>>,
-    pub gasoline: StructOf<Arena<Gasoline>>,
+    pub gasoline: GasolineField,
     pub fire: StructOf<Arena<Fire>>,
     pub explosions: StructOf<Arena<Explosion>>,
     pub particles: StructOf<Arena<Particle>>,
     pub pickups: StructOf<Arena<PickUp>>,
+    /// Pairs of touching entities, to avoid applying contact damage every tick.
+    pub contacts: Contacts,
+    /// Pairs of the player in barrel form and the actors they run over,
+    /// separate from `contacts`, so that the cooldowns of the two forms do not affect each other.
+    pub barrel_contacts: Contacts,
+    /// Pairs of the player and the blocks they ram, to damage the blocks once per contact.
+    /// Separate from `contacts`, since the block ids come from another arena.
+    pub block_contacts: Contacts,
+    /// Paths for the enemies towards the player around the blocks.
+    /// Not saved in snapshots, it gets rebuilt from the blocks instead.
+    pub flow_field: FlowField,
     pub queued_effects: VecDeque<QueuedEffect>,
+    /// Delayed parts of the shot patterns.
+    pub queued_shots: Vec<QueuedShot>,
     pub game_events: Vec<GameEvent>,
 }
 
@@ -61,28 +95,50 @@ impl Model {
         config: Config,
         level: LevelConfig,
         enemies: HashMap<String, EnemyConfig>,
+        bosses: HashMap<String, BossConfig>,
+        weapons: HashMap<String, GunConfig>,
         waves: WavesConfig,
+        seed: u64,
     ) -> Self {
         let mut actors = StructOf::<Arena<Actor>>::default();
         let mut model = Self {
             theme,
+            seed,
+            rng: SimRng::seed_from_u64(seed),
             time: Time::ZERO,
             time_alive: Time::ZERO,
+            kills: 0,
             screen_shake: ScreenShake::new(),
             camera: Camera::new(config.camera.fov, config.world_size),
-            player: Player::init(config.player.clone(), config.world_size, &mut actors),
+            player: Player::init(
+                config.player.clone(),
+                config.world_size,
+                &weapons,
+                &mut actors,
+            ),
             actors,
             blocks: default(),
             background_blocks: default(),
             projectiles: default(),
-            gasoline: default(),
+            gasoline: GasolineField::new(
+                config.world_size,
+                config.player.barrel_state.gasoline.field.clone(),
+            ),
             fire: default(),
             explosions: default(),
             particles: default(),
             pickups: default(),
             wave_manager: WaveManager::new(waves.clone(), config.world_size),
             enemies_list: enemies,
+            boss: None,
+            bosses,
+            weapons,
+            contacts: Contacts::default(),
+            barrel_contacts: Contacts::default(),
+            block_contacts: Contacts::default(),
+            flow_field: FlowField::new(config.world_size, config.pathfinding.cell_size),
             queued_effects: VecDeque::new(),
+            queued_shots: Vec::new(),
             game_events: Vec::new(),
             config,
             level,
@@ -93,8 +149,8 @@ impl Model {
     }
 
     fn init(&mut self) {
-        // TODO: navmesh
         self.generate_level();
+        self.flow_field.invalidate_obstacles();
     }
 
     /// Revive the player.
@@ -102,18 +158,22 @@ impl Model {
         self.player = Player::init(
             self.config.player.clone(),
             self.config.world_size,
+            &self.weapons,
             &mut self.actors,
         );
     }
 
-    /// Restart the whole game.
-    pub fn reset(&mut self) {
+    /// Restart the whole game with the given seed.
+    pub fn reset(&mut self, seed: u64) {
         *self = Self::new(
             self.theme.clone(),
             self.config.clone(),
             self.level.clone(),
             self.enemies_list.clone(),
+            self.bosses.clone(),
+            self.weapons.clone(),
             self.waves.clone(),
+            seed,
         );
     }
 }
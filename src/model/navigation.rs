@@ -0,0 +1,202 @@
+use super::*;
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+
+/// A torus-aware flow field for enemy navigation.
+/// Each cell stores the length of the shortest path to the target around the obstacles,
+/// so actors can follow it by moving to the neighbouring cell closest to the target.
+#[derive(Debug, Clone)]
+pub struct FlowField {
+    world_size: vec2<Coord>,
+    /// Number of cells along each axis.
+    grid_size: vec2<i64>,
+    cell_size: vec2<Coord>,
+    blocked: Vec<bool>,
+    /// Path length to the target, `None` for unreachable cells.
+    distance: Vec<Option<u32>>,
+    target: Option<vec2<i64>>,
+    obstacles_outdated: bool,
+}
+
+impl FlowField {
+    /// The cell size gets adjusted so that the cells tile the world exactly.
+    pub fn new(world_size: vec2<Coord>, cell_size: Coord) -> Self {
+        let grid_size = world_size.map(|size| (size / cell_size).as_f32().floor().max(1.0) as i64);
+        let cells = usize::try_from(grid_size.x * grid_size.y).unwrap();
+        Self {
+            world_size,
+            grid_size,
+            cell_size: vec2(
+                world_size.x / r32(grid_size.x as f32),
+                world_size.y / r32(grid_size.y as f32),
+            ),
+            blocked: vec![false; cells],
+            distance: vec![None; cells],
+            target: None,
+            obstacles_outdated: true,
+        }
+    }
+
+    /// Request the obstacles to be updated, because blocks have been added or removed.
+    pub fn invalidate_obstacles(&mut self) {
+        self.obstacles_outdated = true;
+    }
+
+    pub fn obstacles_outdated(&self) -> bool {
+        self.obstacles_outdated
+    }
+
+    /// Mark the cells near the obstacles as blocked.
+    /// `margin` is the extra distance to keep away from the obstacles.
+    pub fn set_obstacles<'a>(
+        &mut self,
+        obstacles: impl IntoIterator<Item = &'a Collider>,
+        margin: Coord,
+    ) {
+        self.obstacles_outdated = false;
+        // Force the distances to be recalculated
+        self.target = None;
+        self.blocked.fill(false);
+
+        let cell_radius = self.cell_size.len() / r32(2.0);
+        for obstacle in obstacles {
+            let reach = obstacle.shape.bounding_radius() + cell_radius + margin;
+            let reach = vec2(
+                (reach / self.cell_size.x).as_f32().ceil() as i64,
+                (reach / self.cell_size.y).as_f32().ceil() as i64,
+            );
+            let center = self.to_cell(obstacle.position);
+            for dx in -reach.x..=reach.x {
+                for dy in -reach.y..=reach.y {
+                    let cell = center + vec2(dx, dy);
+                    let index = self.index(cell);
+                    if self.blocked[index] {
+                        continue;
+                    }
+                    let cell_collider = Collider::new(
+                        self.cell_center(cell),
+                        Shape::Circle {
+                            radius: cell_radius + margin,
+                        },
+                    );
+                    if cell_collider.check(obstacle) {
+                        self.blocked[index] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Recalculate the paths towards the target, if it has moved to a different cell.
+    pub fn update_target(&mut self, target: Position) {
+        let target = self.wrap(self.to_cell(target));
+        if self.target == Some(target) {
+            return;
+        }
+        self.target = Some(target);
+
+        // Dijkstra from the target
+        self.distance.fill(None);
+        let index = self.index(target);
+        self.distance[index] = Some(0);
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse((0, target.x, target.y)));
+        while let Some(Reverse((dist, x, y))) = queue.pop() {
+            let cell = vec2(x, y);
+            if self.distance[self.index(cell)].is_some_and(|best| best < dist) {
+                continue;
+            }
+            for (next, cost) in self.neighbours(cell).into_iter().flatten() {
+                let index = self.index(next);
+                let next_dist = dist + cost;
+                if self.distance[index].is_none_or(|best| next_dist < best) {
+                    self.distance[index] = Some(next_dist);
+                    queue.push(Reverse((next_dist, next.x, next.y)));
+                }
+            }
+        }
+    }
+
+    /// Direction to follow from the position along the shortest path to the target.
+    /// Returns `None` if the position is in the same cell as the target,
+    /// or if there is no path from it.
+    pub fn direction(&self, position: Position) -> Option<vec2<Coord>> {
+        let cell = self.to_cell(position);
+        let current = self.distance[self.index(cell)]?;
+        if current == 0 {
+            return None;
+        }
+
+        let (next, _) = self
+            .neighbours(cell)
+            .into_iter()
+            .flatten()
+            .filter_map(|(next, _)| self.distance[self.index(next)].map(|dist| (next, dist)))
+            .min_by_key(|&(_, dist)| dist)?;
+        Some(
+            position
+                .delta_to(self.cell_center(next))
+                .normalize_or_zero(),
+        )
+    }
+
+    /// Free neighbouring cells with the cost of moving there, `None` for the blocked ones.
+    /// Diagonal moves are not allowed to cut the corners of the obstacles.
+    /// Returns an array rather than a `Vec`, since it is called for every cell on every rebuild.
+    fn neighbours(&self, cell: vec2<i64>) -> [Option<(vec2<i64>, u32)>; 8] {
+        const DELTAS: [(i64, i64); 8] = [
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+            (0, -1),
+            (0, 1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+        ];
+        let is_free = |delta: vec2<i64>| !self.blocked[self.index(cell + delta)];
+        DELTAS.map(|(dx, dy)| {
+            let delta = vec2(dx, dy);
+            if !is_free(delta) {
+                return None;
+            }
+            if dx != 0 && dy != 0 {
+                (is_free(vec2(dx, 0)) && is_free(vec2(0, dy)))
+                    .then(|| (self.wrap(cell + delta), DIAGONAL_COST))
+            } else {
+                Some((self.wrap(cell + delta), STRAIGHT_COST))
+            }
+        })
+    }
+
+    fn to_cell(&self, position: Position) -> vec2<i64> {
+        let position = position.to_world();
+        self.wrap(vec2(
+            (position.x / self.cell_size.x).as_f32().floor() as i64,
+            (position.y / self.cell_size.y).as_f32().floor() as i64,
+        ))
+    }
+
+    fn cell_center(&self, cell: vec2<i64>) -> Position {
+        let center = vec2(
+            (r32(cell.x as f32) + r32(0.5)) * self.cell_size.x,
+            (r32(cell.y as f32) + r32(0.5)) * self.cell_size.y,
+        );
+        Position::from_world(center, self.world_size)
+    }
+
+    fn wrap(&self, cell: vec2<i64>) -> vec2<i64> {
+        vec2(
+            cell.x.rem_euclid(self.grid_size.x),
+            cell.y.rem_euclid(self.grid_size.y),
+        )
+    }
+
+    fn index(&self, cell: vec2<i64>) -> usize {
+        let cell = self.wrap(cell);
+        usize::try_from(cell.y * self.grid_size.x + cell.x).unwrap()
+    }
+}
@@ -7,23 +7,26 @@ pub struct Player {
     pub out_of_view: bool,
     pub state: PlayerState,
     pub gasoline: Bounded<R32>,
+    /// The loadout. The state of the active weapon is kept in the gun of the player actor.
+    pub weapons: Vec<Weapon>,
+    pub active_weapon: usize,
 }
 
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PlayerInput {
     pub aim_at: Position,
     pub direction: vec2<Coord>,
     pub drip_gas: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum PlayerState {
     Human,
     Barrel { last_gas: Position },
 }
 
 impl Player {
-    pub fn new(actor: Id, world_size: vec2<Coord>) -> Self {
+    pub fn new(actor: Id, world_size: vec2<Coord>, weapons: Vec<Weapon>) -> Self {
         Self {
             actor,
             input: PlayerInput {
@@ -34,25 +37,42 @@ impl Player {
             out_of_view: false,
             state: PlayerState::Human,
             gasoline: Bounded::new(R32::ZERO, R32::ZERO..=r32(100.0)),
+            weapons,
+            active_weapon: 0,
         }
     }
 
     pub fn init(
         config: PlayerConfig,
         world_size: vec2<Coord>,
+        weapons: &HashMap<String, GunConfig>,
         actors: &mut StructOf<Arena<Actor>>,
     ) -> Self {
-        let actor = actors.insert(
-            Actor::new(
-                Body::new(Position::zero(world_size), config.human_state.body),
-                config.hp,
-                config.acceleration,
-                Fraction::Player,
-                config.human_state.stats,
-                ActorKind::Player,
-            )
-            .with_gun(config.gun),
+        let weapons: Vec<Weapon> = config
+            .weapons
+            .iter()
+            .map(|name| Weapon {
+                name: name.clone(),
+                gun: Gun::new(
+                    weapons
+                        .get(name)
+                        .unwrap_or_else(|| panic!("Weapon {:?} not found", name))
+                        .clone(),
+                ),
+            })
+            .collect();
+        let mut actor = Actor::new(
+            Body::new(Position::zero(world_size), config.human_state.body),
+            config.hp,
+            config.acceleration,
+            Fraction::Player,
+            config.human_state.stats,
+            ActorKind::Player,
         );
-        Self::new(actor, world_size)
+        if let Some(weapon) = weapons.first() {
+            actor = actor.with_gun(weapon.gun.config.clone());
+        }
+        let actor = actors.insert(actor);
+        Self::new(actor, world_size, weapons)
     }
 }
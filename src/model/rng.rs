@@ -0,0 +1,58 @@
+use super::*;
+
+/// The simulation rng (xoshiro256++).
+///
+/// Unlike [StdRng], its state can be saved in snapshots
+/// without disturbing the running simulation.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SimRng {
+    state: [u64; 4],
+}
+
+impl RngCore for SimRng {
+    fn next_u32(&mut self) -> u32 {
+        // The upper bits are of higher quality
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let [s0, s1, s2, s3] = &mut self.state;
+        let result = s0.wrapping_add(*s3).rotate_left(23).wrapping_add(*s0);
+        let t = *s1 << 17;
+        *s2 ^= *s0;
+        *s3 ^= *s1;
+        *s1 ^= *s2;
+        *s0 ^= *s3;
+        *s2 ^= t;
+        *s3 = s3.rotate_left(45);
+        result
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for SimRng {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut state = [0; 4];
+        for (value, bytes) in state.iter_mut().zip(seed.chunks_exact(8)) {
+            *value = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+        if state == [0; 4] {
+            // The all-zero state only ever produces zeros
+            return Self::seed_from_u64(0);
+        }
+        Self { state }
+    }
+}
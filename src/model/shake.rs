@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ScreenShake {
     pub duration: Time,
     pub amplitude: Coord,
@@ -25,14 +25,14 @@ impl ScreenShake {
         };
     }
 
-    pub fn get(&self) -> vec2<Coord> {
-        let dir = Angle::from_degrees(r32(thread_rng().gen_range(0.0..360.0)));
+    pub fn get(&self, rng: &mut impl Rng) -> vec2<Coord> {
+        let dir = Angle::from_degrees(r32(rng.gen_range(0.0..360.0)));
         let amplitude = self.amplitude * self.duration.min(Time::ONE);
         dir.unit_vec() * amplitude
     }
 
-    pub fn apply_to_camera(&mut self, camera: &mut Camera, delta_time: Time) {
-        let velocity = self.get();
+    pub fn apply_to_camera(&mut self, camera: &mut Camera, rng: &mut impl Rng, delta_time: Time) {
+        let velocity = self.get(rng);
         camera.center.shift(velocity * delta_time);
     }
 }
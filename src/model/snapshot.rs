@@ -0,0 +1,456 @@
+use super::*;
+
+use std::{cell::RefCell, path::Path};
+
+/// The whole state of the [Model], which can be saved to disk and restored later.
+#[derive(Serialize, Deserialize)]
+pub struct ModelSnapshot {
+    pub theme: Theme,
+    pub rng: SimRng,
+    pub seed: u64,
+    pub time: Time,
+    pub time_alive: Time,
+    pub kills: usize,
+    pub config: Config,
+    pub level: LevelConfig,
+    pub waves: WavesConfig,
+    pub screen_shake: ScreenShake,
+    pub camera: Camera,
+    pub enemies_list: HashMap<String, EnemyConfig>,
+    pub bosses: HashMap<String, BossConfig>,
+    pub weapons: HashMap<String, GunConfig>,
+    pub wave_manager: WaveManager,
+    pub boss: Option<BossState>,
+    pub player: PlayerSnapshot,
+    pub actors: Vec<Actor>,
+    pub blocks: Vec<Block>,
+    pub background_blocks: Vec<Block>,
+    pub projectiles: Vec<Projectile>,
+    pub gasoline: GasolineField,
+    pub fire: Vec<Fire>,
+    pub explosions: Vec<Explosion>,
+    pub particles: Vec<Particle>,
+    pub pickups: Vec<PickUp>,
+    pub queued_effects: VecDeque<QueuedEffect>,
+    pub queued_shots: Vec<QueuedShot>,
+    /// Pairs of the touching actors.
+    pub contacts: ContactsSnapshot,
+    pub barrel_contacts: ContactsSnapshot,
+    /// Pairs of the player and the blocks they ram,
+    /// with the blocks referenced by their index in [ModelSnapshot::blocks].
+    pub block_contacts: ContactsSnapshot,
+    /// Ids of the [ModelSnapshot::actors] as referenced by the rest of the snapshot.
+    #[serde(skip)]
+    pub actor_ids: Vec<Id>,
+}
+
+/// Arena ids are not preserved between snapshots,
+/// so the player's actor is referenced by its index in [ModelSnapshot::actors].
+#[derive(Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+    pub actor: usize,
+    pub input: PlayerInput,
+    pub out_of_view: bool,
+    pub state: PlayerState,
+    pub gasoline: Bounded<R32>,
+    pub weapons: Vec<Weapon>,
+    pub active_weapon: usize,
+}
+
+impl ModelSnapshot {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let data = std::fs::read_to_string(path).context(format!("when loading {:?}", path))?;
+        let (snapshot, actor_ids) = snapshot_id::loading(|| ron::from_str::<Self>(&data));
+        let mut snapshot = snapshot.context(format!("when loading {:?}", path))?;
+        snapshot.actor_ids = actor_ids;
+        Ok(snapshot)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let data = snapshot_id::saving(&self.actor_ids, || ron::ser::to_string(self))?;
+        std::fs::write(path, data).context(format!("when saving {:?}", path))?;
+        Ok(())
+    }
+}
+
+/// Arena ids cannot be serialized, so in snapshots they are stored
+/// as indices into [ModelSnapshot::actors] and remapped in [Model::from_snapshot].
+/// Use with `#[serde(with = "snapshot_id::option")]` on the fields holding actor ids.
+///
+/// The indices are looked up in a context, which is only set up
+/// by [ModelSnapshot::save] and [ModelSnapshot::load].
+/// Outside of them, the ids are serialized as `None` (or skipped in a `Vec`),
+/// and deserializing them fails.
+pub mod snapshot_id {
+    use super::*;
+
+    thread_local! {
+        static CONTEXT: RefCell<Option<Context>> = const { RefCell::new(None) };
+    }
+
+    enum Context {
+        /// Indices of the actors being saved.
+        Save(HashMap<Id, usize>),
+        /// Placeholder ids handed out for the indices being loaded.
+        Load { arena: Arena<()>, ids: Vec<Id> },
+    }
+
+    /// Sets up the context for its lifetime,
+    /// so that it gets reset even if the (de)serialization panics.
+    struct ContextGuard;
+
+    impl ContextGuard {
+        fn new(context: Context) -> Self {
+            CONTEXT.with(|cell| {
+                let mut cell = cell.borrow_mut();
+                assert!(cell.is_none(), "Nested snapshot (de)serialization");
+                *cell = Some(context);
+            });
+            Self
+        }
+
+        fn take(self) -> Option<Context> {
+            CONTEXT.with(|cell| cell.borrow_mut().take())
+        }
+    }
+
+    impl Drop for ContextGuard {
+        fn drop(&mut self) {
+            CONTEXT.with(|cell| *cell.borrow_mut() = None);
+        }
+    }
+
+    /// Serialize the ids as the indices of the actors.
+    pub(super) fn saving<T>(actor_ids: &[Id], f: impl FnOnce() -> T) -> T {
+        let indices = actor_ids
+            .iter()
+            .enumerate()
+            .map(|(index, &id)| (id, index))
+            .collect();
+        let _guard = ContextGuard::new(Context::Save(indices));
+        f()
+    }
+
+    /// Deserialize the indices as placeholder ids, returned in the index order.
+    pub(super) fn loading<T>(f: impl FnOnce() -> T) -> (T, Vec<Id>) {
+        let guard = ContextGuard::new(Context::Load {
+            arena: Arena::default(),
+            ids: Vec::new(),
+        });
+        let result = f();
+        let ids = match guard.take() {
+            Some(Context::Load { ids, .. }) => ids,
+            _ => Vec::new(),
+        };
+        (result, ids)
+    }
+
+    /// Ids of the actors that are gone, or outside of a snapshot, are forgotten.
+    fn to_index(id: Id) -> Option<usize> {
+        CONTEXT.with(|context| match &*context.borrow() {
+            Some(Context::Save(indices)) => indices.get(&id).copied(),
+            _ => None,
+        })
+    }
+
+    fn from_index(index: usize) -> Option<Id> {
+        CONTEXT.with(|context| match &mut *context.borrow_mut() {
+            Some(Context::Load { arena, ids }) => {
+                while ids.len() <= index {
+                    ids.push(arena.insert(()));
+                }
+                Some(ids[index])
+            }
+            _ => None,
+        })
+    }
+
+    /// For `Option<Id>` fields.
+    /// Only meaningful inside [ModelSnapshot::save] and [ModelSnapshot::load], see [snapshot_id].
+    pub mod option {
+        use super::*;
+
+        use serde::{de::Error, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(id: &Option<Id>, serializer: S) -> Result<S::Ok, S::Error> {
+            id.and_then(to_index).serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Id>, D::Error> {
+            Option::<usize>::deserialize(deserializer)?
+                .map(|index| {
+                    from_index(index)
+                        .ok_or_else(|| D::Error::custom("actor ids outside of a snapshot"))
+                })
+                .transpose()
+        }
+    }
+
+    /// For `Vec<Id>` fields.
+    /// Only meaningful inside [ModelSnapshot::save] and [ModelSnapshot::load], see [snapshot_id].
+    pub mod vec {
+        use super::*;
+
+        use serde::{de::Error, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(ids: &[Id], serializer: S) -> Result<S::Ok, S::Error> {
+            let indices: Vec<usize> = ids.iter().copied().filter_map(to_index).collect();
+            indices.serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Vec<Id>, D::Error> {
+            Vec::<usize>::deserialize(deserializer)?
+                .into_iter()
+                .map(|index| {
+                    from_index(index)
+                        .ok_or_else(|| D::Error::custom("actor ids outside of a snapshot"))
+                })
+                .collect()
+        }
+    }
+}
+
+impl Model {
+    /// Take a snapshot of the whole simulation state.
+    /// Returns `None` if the player is dead, since the run is over anyway.
+    pub fn snapshot(&self) -> Option<ModelSnapshot> {
+        let actor_ids: Vec<Id> = self.actors.health.iter().map(|(id, _)| id).collect();
+        let player_actor = actor_ids.iter().position(|&id| id == self.player.actor)?;
+        let actor_indices: HashMap<Id, usize> = actor_ids
+            .iter()
+            .enumerate()
+            .map(|(index, &id)| (id, index))
+            .collect();
+        let block_indices: HashMap<Id, usize> = self
+            .blocks
+            .kind
+            .iter()
+            .enumerate()
+            .map(|(index, (id, _))| (id, index))
+            .collect();
+
+        Some(ModelSnapshot {
+            theme: self.theme.clone(),
+            rng: self.rng.clone(),
+            seed: self.seed,
+            time: self.time,
+            time_alive: self.time_alive,
+            kills: self.kills,
+            config: self.config.clone(),
+            level: self.level.clone(),
+            waves: self.waves.clone(),
+            screen_shake: self.screen_shake.clone(),
+            camera: self.camera.clone(),
+            enemies_list: self.enemies_list.clone(),
+            bosses: self.bosses.clone(),
+            weapons: self.weapons.clone(),
+            wave_manager: self.wave_manager.clone(),
+            boss: self.boss.clone(),
+            player: PlayerSnapshot {
+                actor: player_actor,
+                input: self.player.input.clone(),
+                out_of_view: self.player.out_of_view,
+                state: self.player.state.clone(),
+                gasoline: self.player.gasoline,
+                weapons: self.player.weapons.clone(),
+                active_weapon: self.player.active_weapon,
+            },
+            actors: actor_ids
+                .iter()
+                .map(|&id| self.actors.get(id).unwrap().clone())
+                .collect(),
+            blocks: collect_blocks(&self.blocks),
+            background_blocks: collect_blocks(&self.background_blocks),
+            projectiles: self
+                .projectiles
+                .lifetime
+                .iter()
+                .map(|(id, _)| self.projectiles.get(id).unwrap().clone())
+                .collect(),
+            gasoline: self.gasoline.clone(),
+            fire: self
+                .fire
+                .lifetime
+                .iter()
+                .map(|(id, _)| self.fire.get(id).unwrap().clone())
+                .collect(),
+            explosions: self
+                .explosions
+                .lifetime
+                .iter()
+                .map(|(id, _)| self.explosions.get(id).unwrap().clone())
+                .collect(),
+            particles: self
+                .particles
+                .lifetime
+                .iter()
+                .map(|(id, _)| self.particles.get(id).unwrap().clone())
+                .collect(),
+            pickups: self
+                .pickups
+                .lifetime
+                .iter()
+                .map(|(id, _)| self.pickups.get(id).unwrap().clone())
+                .collect(),
+            queued_effects: self.queued_effects.clone(),
+            queued_shots: self.queued_shots.clone(),
+            contacts: self.contacts.to_snapshot(&actor_indices, &actor_indices),
+            barrel_contacts: self
+                .barrel_contacts
+                .to_snapshot(&actor_indices, &actor_indices),
+            block_contacts: self
+                .block_contacts
+                .to_snapshot(&actor_indices, &block_indices),
+            actor_ids,
+        })
+    }
+
+    /// Restore the simulation from a snapshot.
+    pub fn from_snapshot(snapshot: ModelSnapshot) -> Self {
+        let mut actors = StructOf::<Arena<Actor>>::default();
+        let actor_ids: Vec<Id> = snapshot
+            .actors
+            .into_iter()
+            .map(|actor| actors.insert(actor))
+            .collect();
+
+        // Point the references to the actors to their new ids
+        let ids: HashMap<Id, Id> = snapshot
+            .actor_ids
+            .iter()
+            .copied()
+            .zip(actor_ids.iter().copied())
+            .collect();
+        for (last_hit, statuses) in query!(actors, (&mut last_hit, &mut statuses)) {
+            *last_hit = last_hit.map(|source| source.remap_owner(&ids));
+            statuses.remap_owners(&ids);
+        }
+
+        let player = snapshot.player;
+        let player = Player {
+            actor: actor_ids[player.actor],
+            input: player.input,
+            out_of_view: player.out_of_view,
+            state: player.state,
+            gasoline: player.gasoline,
+            weapons: player.weapons,
+            active_weapon: player.active_weapon,
+        };
+
+        let mut projectiles = StructOf::<Arena<Projectile>>::default();
+        for mut proj in snapshot.projectiles {
+            proj.owner = proj.owner.and_then(|owner| ids.get(&owner).copied());
+            proj.pierced = proj
+                .pierced
+                .iter()
+                .filter_map(|id| ids.get(id).copied())
+                .collect();
+            projectiles.insert(proj);
+        }
+        let mut fire = StructOf::<Arena<Fire>>::default();
+        for mut fire_item in snapshot.fire {
+            fire_item.source = fire_item.source.remap_owner(&ids);
+            fire.insert(fire_item);
+        }
+        let mut explosions = StructOf::<Arena<Explosion>>::default();
+        for expl in snapshot.explosions {
+            explosions.insert(expl);
+        }
+        let mut particles = StructOf::<Arena<Particle>>::default();
+        for particle in snapshot.particles {
+            particles.insert(particle);
+        }
+        let mut pickups = StructOf::<Arena<PickUp>>::default();
+        for pickup in snapshot.pickups {
+            pickups.insert(pickup);
+        }
+
+        let mut gasoline = snapshot.gasoline;
+        gasoline.remap_owners(&ids);
+        let mut queued_effects = snapshot.queued_effects;
+        for queued in &mut queued_effects {
+            if let Effect::Explosion { source, .. } = &mut queued.effect {
+                *source = source.remap_owner(&ids);
+            }
+        }
+        let (blocks, block_ids) = insert_blocks(snapshot.blocks, &ids);
+        let (background_blocks, _) = insert_blocks(snapshot.background_blocks, &ids);
+        let contacts = Contacts::from_snapshot(snapshot.contacts, &actor_ids, &actor_ids);
+        let barrel_contacts =
+            Contacts::from_snapshot(snapshot.barrel_contacts, &actor_ids, &actor_ids);
+        let block_contacts =
+            Contacts::from_snapshot(snapshot.block_contacts, &actor_ids, &block_ids);
+
+        let mut queued_shots = snapshot.queued_shots;
+        for shot in &mut queued_shots {
+            shot.owner = shot.owner.and_then(|owner| ids.get(&owner).copied());
+        }
+
+        Self {
+            theme: snapshot.theme,
+            seed: snapshot.seed,
+            rng: snapshot.rng,
+            time: snapshot.time,
+            time_alive: snapshot.time_alive,
+            kills: snapshot.kills,
+            config: snapshot.config,
+            level: snapshot.level,
+            waves: snapshot.waves,
+            screen_shake: snapshot.screen_shake,
+            camera: snapshot.camera,
+            enemies_list: snapshot.enemies_list,
+            bosses: snapshot.bosses,
+            weapons: snapshot.weapons,
+            wave_manager: snapshot.wave_manager,
+            boss: snapshot.boss,
+            player,
+            actors,
+            blocks,
+            background_blocks,
+            projectiles,
+            gasoline,
+            fire,
+            explosions,
+            particles,
+            pickups,
+            contacts,
+            barrel_contacts,
+            block_contacts,
+            flow_field: FlowField::new(
+                snapshot.config.world_size,
+                snapshot.config.pathfinding.cell_size,
+            ),
+            queued_effects,
+            queued_shots,
+            game_events: Vec::new(),
+        }
+    }
+}
+
+fn collect_blocks(blocks: &StructOf<Arena<Block>>) -> Vec<Block> {
+    blocks
+        .kind
+        .iter()
+        .map(|(id, _)| blocks.get(id).unwrap().clone())
+        .collect()
+}
+
+/// Returns the blocks along with their new ids in the original order.
+fn insert_blocks(blocks: Vec<Block>, ids: &HashMap<Id, Id>) -> (StructOf<Arena<Block>>, Vec<Id>) {
+    let mut result = StructOf::<Arena<Block>>::default();
+    let block_ids = blocks
+        .into_iter()
+        .map(|mut block| {
+            block.last_hit = block.last_hit.map(|source| source.remap_owner(ids));
+            block.statuses.remap_owners(ids);
+            result.insert(block)
+        })
+        .collect();
+    (result, block_ids)
+}
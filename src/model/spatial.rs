@@ -0,0 +1,84 @@
+use super::*;
+
+/// A torus-aware spatial hash, used as the broadphase for collision checks.
+/// Colliders are approximated by their bounding circles,
+/// so the exact check still has to be done on the candidates.
+#[derive(Debug, Clone)]
+pub struct SpatialHash {
+    /// Number of cells along each axis.
+    grid_size: vec2<i64>,
+    cell_size: vec2<Coord>,
+    cells: HashMap<vec2<i64>, Vec<Id>>,
+}
+
+impl SpatialHash {
+    /// The cell size gets adjusted so that the cells tile the world exactly,
+    /// which keeps the wrap-around correct.
+    pub fn new(world_size: vec2<Coord>, cell_size: Coord) -> Self {
+        let grid_size = world_size.map(|size| (size / cell_size).as_f32().floor().max(1.0) as i64);
+        Self {
+            grid_size,
+            cell_size: vec2(
+                world_size.x / r32(grid_size.x as f32),
+                world_size.y / r32(grid_size.y as f32),
+            ),
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn from_colliders(
+        world_size: vec2<Coord>,
+        cell_size: Coord,
+        colliders: impl IntoIterator<Item = (Id, Collider)>,
+    ) -> Self {
+        let mut hash = Self::new(world_size, cell_size);
+        for (id, collider) in colliders {
+            hash.insert(id, &collider);
+        }
+        hash
+    }
+
+    pub fn insert(&mut self, id: Id, collider: &Collider) {
+        for cell in self.covered_cells(collider) {
+            self.cells.entry(cell).or_default().push(id);
+        }
+    }
+
+    /// Returns the ids of all colliders that might intersect the given one, without duplicates.
+    pub fn query(&self, collider: &Collider) -> Vec<Id> {
+        let mut result: Vec<Id> = self
+            .covered_cells(collider)
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .collect();
+        result.sort();
+        result.dedup();
+        result
+    }
+
+    fn covered_cells(&self, collider: &Collider) -> impl Iterator<Item = vec2<i64>> {
+        let radius = collider.shape.bounding_radius();
+        let position = collider.position.to_world();
+        let cell_size = self.cell_size;
+        let to_cell = |pos: vec2<Coord>| {
+            vec2(
+                (pos.x / cell_size.x).as_f32().floor() as i64,
+                (pos.y / cell_size.y).as_f32().floor() as i64,
+            )
+        };
+
+        let grid_size = self.grid_size;
+        let min = to_cell(position - vec2::splat(radius));
+        let max = to_cell(position + vec2::splat(radius));
+        // Do not cover the same cell twice when the collider is larger than the world
+        let max = vec2(
+            max.x.min(min.x + grid_size.x - 1),
+            max.y.min(min.y + grid_size.y - 1),
+        );
+
+        (min.x..=max.x).flat_map(move |x| {
+            (min.y..=max.y).map(move |y| vec2(x.rem_euclid(grid_size.x), y.rem_euclid(grid_size.y)))
+        })
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::VecDeque;
 
 use super::*;
 
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct WaveManager {
     pub difficulty: R32,
     pub wave_number: usize,
@@ -12,6 +12,9 @@ pub struct WaveManager {
     pub wave_delay: Time,
     pub spawn_delay: Time,
     // pub current_enemies: Vec<Id>,
+    /// Waves started, whose enemies have not all been killed yet.
+    #[serde(default)]
+    pub uncleared: Vec<usize>,
     /// The point for spawning the wave's enemies around.
     pub spawn_point: Position,
 }
@@ -25,6 +28,7 @@ impl WaveManager {
             wave_delay: Time::ZERO,
             spawn_delay: Time::ZERO,
             // current_enemies: Vec::new(),
+            uncleared: Vec::new(),
             current_wave: WaveConfig {
                 spawn_delay: Time::ZERO,
                 wait_for_deaths: false,
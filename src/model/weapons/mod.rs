@@ -3,20 +3,118 @@ use super::*;
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum ShotPattern {
     Single,
-    Multiple { spread_degrees: R32, bullets: usize },
+    Multiple {
+        spread_degrees: R32,
+        bullets: usize,
+    },
+    /// Evenly spaced bullets around the full circle, starting at the aim.
+    Ring {
+        bullets: usize,
+    },
+    /// Bullets in random directions within the cone, with a random speed.
+    Random {
+        spread_degrees: R32,
+        bullets: usize,
+        /// Fraction of the speed the bullets can be faster or slower by.
+        speed_variance: R32,
+    },
+    /// The pattern fired several times, `delay` apart.
+    Burst {
+        shots: usize,
+        delay: Time,
+        pattern: Box<ShotPattern>,
+    },
+    /// Like [ShotPattern::Burst], but every next shot is rotated further by `step_degrees`.
+    Spiral {
+        shots: usize,
+        delay: Time,
+        step_degrees: R32,
+        pattern: Box<ShotPattern>,
+    },
+    /// Several patterns fired together.
+    Composite {
+        patterns: Vec<ShotPattern>,
+    },
 }
 
-#[derive(Debug, Clone)]
+/// A part of a shot pattern waiting for its turn.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QueuedShot {
+    pub delay: Time,
+    pub position: Position,
+    pub aim: Angle<R32>,
+    /// Distance to the aimed point, where the thrown projectiles land.
+    /// If not set, they are thrown as far as they can fly.
+    pub range: Option<Coord>,
+    pub fraction: Fraction,
+    #[serde(with = "snapshot_id::option")]
+    pub owner: Option<Id>,
+    /// Whether the shot moves with the owner and gets cancelled if the owner dies.
+    pub follow_owner: bool,
+    pub pattern: ShotPattern,
+    pub projectile: ProjectileConfig,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Gun {
     pub config: GunConfig,
     pub shot_delay: Time,
+    /// Shots left before reloading, `None` for unlimited ammo.
+    pub ammo: Option<usize>,
+    /// Time left until the reload is finished, `None` if not reloading.
+    pub reload: Option<Time>,
+}
+
+/// A weapon in the loadout of the player.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Weapon {
+    pub name: String,
+    pub gun: Gun,
 }
 
 impl Gun {
     pub fn new(config: GunConfig) -> Self {
         Self {
+            ammo: config.ammo.map(|ammo| ammo.capacity),
             config,
             shot_delay: Time::ZERO,
+            reload: None,
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.shot_delay <= Time::ZERO && self.reload.is_none() && self.ammo != Some(0)
+    }
+
+    /// Spend a shot, starting to reload once the ammo runs out.
+    pub fn fire(&mut self) {
+        self.shot_delay = self.config.shot_delay;
+        if let Some(ammo) = &mut self.ammo {
+            *ammo = ammo.saturating_sub(1);
+            if *ammo == 0 {
+                self.start_reload();
+            }
+        }
+    }
+
+    /// Start reloading, unless the gun is already full or reloading.
+    pub fn start_reload(&mut self) {
+        let Some(config) = self.config.ammo else {
+            return;
+        };
+        if self.reload.is_none() && self.ammo != Some(config.capacity) {
+            self.reload = Some(config.reload_time);
+        }
+    }
+
+    pub fn update(&mut self, delta_time: Time) {
+        self.shot_delay = (self.shot_delay - delta_time).max(Time::ZERO);
+        if let Some(reload) = &mut self.reload {
+            *reload -= delta_time;
+            if *reload <= Time::ZERO {
+                self.reload = None;
+                self.ammo = self.config.ammo.map(|ammo| ammo.capacity);
+            }
         }
     }
 }
@@ -19,11 +19,13 @@ pub struct GameRender {
     world_texture: ugli::Texture,
     fire_texture: ugli::Texture,
     fire_shake: vec2<Coord>,
+    /// Cosmetic randomness, kept separate from the simulation rng.
+    rng: StdRng,
     unit_quad: ugli::VertexBuffer<draw2d::TexturedVertex>,
 }
 
 impl GameRender {
-    pub fn new(geng: &Geng, assets: &Rc<Assets>, theme: Theme) -> Self {
+    pub fn new(geng: &Geng, assets: &Rc<Assets>, theme: Theme, seed: u64) -> Self {
         Self {
             geng: geng.clone(),
             assets: assets.clone(),
@@ -32,6 +34,7 @@ impl GameRender {
             fire_texture: texture_utils::new_texture(geng.ugli(), crate::SCREEN_SIZE),
             world_texture: texture_utils::new_texture(geng.ugli(), crate::SCREEN_SIZE),
             fire_shake: vec2::ZERO,
+            rng: StdRng::seed_from_u64(seed),
             unit_quad: geng_utils::geometry::unit_quad_geometry(geng.ugli()),
         }
     }
@@ -67,7 +70,7 @@ impl GameRender {
         // Tiled fire texture
         {
             let amplitude = r32(1.0);
-            let dir = Angle::from_degrees(r32(thread_rng().gen_range(0.0..360.0)));
+            let dir = Angle::from_degrees(r32(self.rng.gen_range(0.0..360.0)));
             self.fire_shake += dir.unit_vec() * amplitude * delta_time;
         }
         let size = fire_framebuffer.size().as_f32();
@@ -145,6 +148,34 @@ impl GameRender {
                 0.1,
                 self.theme.fire,
             );
+
+            // Active weapon
+            let weapon = model.player.weapons.get(model.player.active_weapon);
+            let gun = model
+                .actors
+                .gun
+                .get(model.player.actor)
+                .and_then(Option::as_ref);
+            if let (Some(weapon), Some(gun)) = (weapon, gun) {
+                let text = match (gun.reload, gun.ammo, gun.config.ammo) {
+                    (Some(_), _, _) => format!("{} (reloading)", weapon.name),
+                    (None, Some(ammo), Some(config)) => {
+                        format!("{} {}/{}", weapon.name, ammo, config.capacity)
+                    }
+                    _ => weapon.name.clone(),
+                };
+                font.draw_with_outline(
+                    framebuffer,
+                    &geng::PixelPerfectCamera,
+                    &text,
+                    vec2(geng::TextAlign::RIGHT, geng::TextAlign(0.0)),
+                    mat3::translate(vec2(framebuffer_size.x - 20.0, 20.0))
+                        * mat3::scale_uniform(40.0),
+                    self.theme.whiteish,
+                    0.1,
+                    self.theme.fire,
+                );
+            }
         } else {
             let text_color = Color::WHITE;
             let outline_color = Color::BLACK;
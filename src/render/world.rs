@@ -59,9 +59,22 @@ impl WorldRender {
 
     fn draw_gasoline(&self, model: &Model, framebuffer: &mut ugli::Framebuffer) {
         let camera = &model.camera;
-        let color = self.theme.gasoline;
-        for collider in query!(model.gasoline, (&collider)) {
-            self.draw_collider(&collider.clone(), color, camera, framebuffer);
+        let config = &model.config.player.barrel_state.gasoline.field;
+        let cell_size = model.gasoline.cell_size();
+        let shape = Shape::Rectangle {
+            width: cell_size.x,
+            height: cell_size.y,
+        };
+        for cell in model.gasoline.cells() {
+            // Shallow gasoline fades out
+            let mut color = self.theme.gasoline;
+            color.a *= (cell.density / config.pool_depth).as_f32().min(1.0);
+            self.draw_collider(
+                &Collider::new(cell.position, shape),
+                color,
+                camera,
+                framebuffer,
+            );
         }
     }
 
@@ -167,6 +180,26 @@ impl WorldRender {
             );
         }
 
+        // Burning gasoline
+        let cell_size = model.gasoline.cell_size();
+        let shape = Shape::Rectangle {
+            width: cell_size.x,
+            height: cell_size.y,
+        };
+        for cell in model.gasoline.cells() {
+            let Some(time) = cell.burning else {
+                continue;
+            };
+            let scale = (time.as_f32() / 0.3).clamp(0.0, 1.0);
+            self.draw_collider_transformed(
+                &Collider::new(cell.position, shape),
+                color,
+                camera,
+                mat3::scale_uniform(scale),
+                framebuffer,
+            );
+        }
+
         struct ExplRef<'a> {
             position: &'a Position,
             max_radius: &'a Coord,
@@ -196,7 +229,8 @@ impl WorldRender {
         let player_index = model.player.actor;
         if let Some(player_actor) = model.actors.get(player_index) {
             let player_body = player_actor.body;
-            let player_position = player_body.collider.position;
+            let player_position =
+                camera.interpolate(*player_body.prev_position, *player_body.collider.position);
 
             let aabb = player_body.collider.clone().compute_aabb();
             let radius = aabb.width().max(aabb.height()).as_f32() + 0.2;
@@ -204,11 +238,7 @@ impl WorldRender {
             self.geng.draw2d().draw2d(
                 framebuffer,
                 camera,
-                &draw2d::Ellipse::circle(
-                    camera.project_f32(*player_position),
-                    radius,
-                    Color::BLACK,
-                ),
+                &draw2d::Ellipse::circle(camera.project_f32(player_position), radius, Color::BLACK),
             );
         }
     }
@@ -216,8 +246,10 @@ impl WorldRender {
     fn draw_actors(&self, model: &Model, framebuffer: &mut ugli::Framebuffer) {
         struct ActorRef<'a> {
             collider: ColliderRef<'a>,
+            prev_position: &'a Position,
             velocity: &'a vec2<Coord>,
             kind: &'a ActorKind,
+            ai: &'a Option<ActorAI>,
         }
 
         let camera = &model.camera;
@@ -225,10 +257,40 @@ impl WorldRender {
             model.actors,
             ActorRef {
                 collider: &body.collider,
+                prev_position: &body.prev_position,
                 velocity: &body.velocity,
-                kind
+                kind,
+                ai,
             }
         ) {
+            let actor_position = camera.interpolate(*actor.prev_position, *actor.collider.position);
+
+            // Warn about the upcoming attack
+            let telegraph = match actor.ai {
+                Some(ActorAI::Charger {
+                    dash_speed,
+                    dash_time,
+                    state: ChargerState::Telegraphing { direction, .. },
+                    ..
+                }) => Some(*direction * *dash_speed * *dash_time),
+                Some(ActorAI::Sniper {
+                    state: SniperState::Aiming { target, .. },
+                    ..
+                }) => Some(actor_position.delta_to(*target)),
+                _ => None,
+            };
+            if let Some(delta) = telegraph {
+                let start = camera.project_f32(actor_position);
+                self.geng.draw2d().draw2d(
+                    framebuffer,
+                    camera,
+                    &draw2d::Segment::new(
+                        Segment(start, start + delta.as_f32()),
+                        0.1,
+                        self.theme.telegraph,
+                    ),
+                );
+            }
             let mut mirror = false;
             let sprite = match actor.kind {
                 ActorKind::Player => match model.player.state {
@@ -241,7 +303,7 @@ impl WorldRender {
                 ActorKind::EnemyHuge => &self.assets.sprites.enemy_huge,
                 ActorKind::BossFoot { leg_offset } => {
                     // Leg
-                    let delta = actor.collider.position.delta_to(Position::from_world(
+                    let delta = actor_position.delta_to(Position::from_world(
                         vec2(0.0, -5.0).as_r32(),
                         model.config.world_size,
                     ));
@@ -257,14 +319,13 @@ impl WorldRender {
                         camera,
                         framebuffer.size().as_f32(),
                     );
-                    let dir =
-                        Position::zero(model.config.world_size).delta_to(*actor.collider.position);
+                    let dir = Position::zero(model.config.world_size).delta_to(actor_position);
                     mirror = dir.x > Coord::ZERO;
                     if mirror {
                         std::mem::swap(&mut position.min.x, &mut position.max.x);
                         let dir =
                             Position::from_world(vec2(0.0, -5.0).as_r32(), model.config.world_size)
-                                .delta_to(*actor.collider.position)
+                                .delta_to(actor_position)
                                 .normalize_or_zero();
                         angle = dir.arg().map(R32::as_f32) / 3.0;
                     }
@@ -281,7 +342,7 @@ impl WorldRender {
             };
 
             let position = geng_utils::pixel::pixel_perfect_aabb(
-                camera.project_f32(*actor.collider.position),
+                camera.project_f32(actor_position),
                 vec2::splat(0.5),
                 sprite.size(),
                 camera,
@@ -353,7 +414,7 @@ impl WorldRender {
             if let ActorKind::BossBody = actor.kind {
                 let eye_sprite = &self.assets.sprites.boss_eye;
                 let position = geng_utils::pixel::pixel_perfect_aabb(
-                    camera.project_f32(*actor.collider.position),
+                    camera.project_f32(actor_position),
                     vec2::splat(0.5),
                     eye_sprite.size(),
                     camera,
@@ -375,7 +436,10 @@ impl WorldRender {
     fn draw_projectiles(&self, model: &Model, framebuffer: &mut ugli::Framebuffer) {
         struct ProjRef<'a> {
             collider: ColliderRef<'a>,
+            prev_position: &'a Position,
             kind: &'a ProjectileKind,
+            target_pos: &'a Option<Position>,
+            thrown: &'a Option<Thrown>,
         }
 
         let camera = &model.camera;
@@ -383,7 +447,10 @@ impl WorldRender {
             model.projectiles,
             ProjRef {
                 collider: &body.collider,
-                kind
+                prev_position: &body.prev_position,
+                kind,
+                target_pos,
+                thrown,
             }
         ) {
             let sprite = match proj.kind {
@@ -393,10 +460,25 @@ impl WorldRender {
                 ProjectileKind::SquareSnowflake => &self.assets.sprites.projectile_square_snowflake,
                 ProjectileKind::SquidLike => &self.assets.sprites.projectile_squid_like,
                 ProjectileKind::WheelPizza => &self.assets.sprites.projectile_wheel_pizza,
+                ProjectileKind::Barrel => &self.assets.sprites.barrel,
             };
 
+            let ground = camera
+                .project_f32(camera.interpolate(*proj.prev_position, *proj.collider.position));
+            let mut height = 0.0;
+            if let (Some(thrown), Some(target)) = (proj.thrown, proj.target_pos) {
+                // Thrown projectiles fly above their shadow
+                height = thrown.height(*proj.collider.position, *target).as_f32();
+                let radius = proj.collider.shape.bounding_radius().as_f32();
+                self.geng.draw2d().draw2d(
+                    framebuffer,
+                    camera,
+                    &draw2d::Ellipse::circle(ground, radius, Color::new(0.0, 0.0, 0.0, 0.4)),
+                );
+            }
+
             let position = geng_utils::pixel::pixel_perfect_aabb(
-                camera.project_f32(*proj.collider.position),
+                ground + vec2(0.0, height),
                 vec2::splat(0.5),
                 sprite.size(),
                 camera,
@@ -442,6 +524,10 @@ impl WorldRender {
                 ParticleKind::Damage => self.theme.health_fg_enemy,
                 ParticleKind::Heal => self.theme.health_fg_player,
                 ParticleKind::Projectile => self.theme.gasoline,
+                ParticleKind::Poison => self.theme.status.poison,
+                ParticleKind::Frost => self.theme.status.frost,
+                ParticleKind::Oil => self.theme.status.oil,
+                ParticleKind::Debris => self.theme.debris,
             };
             let alpha = particle.lifetime.get_ratio().as_f32();
             color.a *= alpha;
@@ -462,6 +548,7 @@ impl WorldRender {
     fn draw_pickups(&self, model: &Model, framebuffer: &mut ugli::Framebuffer) {
         struct PickupRef<'a> {
             collider: ColliderRef<'a>,
+            prev_position: &'a Position,
             kind: &'a PickUpKind,
             lifetime: &'a Lifetime,
         }
@@ -471,6 +558,7 @@ impl WorldRender {
             model.pickups,
             PickupRef {
                 collider: &body.collider,
+                prev_position: &body.prev_position,
                 kind,
                 lifetime
             }
@@ -479,7 +567,11 @@ impl WorldRender {
                 PickUpKind::Heal { .. } => self.theme.pickups.heal,
             };
             color.a *= (2.0 * pickup.lifetime.get_ratio().as_f32()).clamp(0.0, 1.0);
-            self.draw_collider(&pickup.collider.clone(), color, camera, framebuffer);
+            let collider = Collider {
+                position: camera.interpolate(*pickup.prev_position, *pickup.collider.position),
+                ..pickup.collider.clone()
+            };
+            self.draw_collider(&collider, color, camera, framebuffer);
         }
     }
 
@@ -573,6 +665,7 @@ impl WorldRender {
     fn draw_health(&self, model: &Model, framebuffer: &mut ugli::Framebuffer) {
         struct ActorRef<'a> {
             collider: ColliderRef<'a>,
+            prev_position: &'a Position,
             health: &'a Health,
             fraction: &'a Fraction,
         }
@@ -582,6 +675,7 @@ impl WorldRender {
             model.actors,
             ActorRef {
                 collider: &body.collider,
+                prev_position: &body.prev_position,
                 health,
                 fraction
             }
@@ -592,7 +686,8 @@ impl WorldRender {
 
             let aabb = actor.collider.clone().compute_aabb();
             let radius = aabb.width().max(aabb.height()).as_f32() + 0.2;
-            let pos = camera.project_f32(*actor.collider.position);
+            let pos = camera
+                .project_f32(camera.interpolate(*actor.prev_position, *actor.collider.position));
             let color = match actor.fraction {
                 Fraction::Player => self.theme.health_fg_player,
                 Fraction::Enemy => self.theme.health_fg_enemy,
@@ -0,0 +1,167 @@
+use crate::{model::*, prelude::*};
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// Everything needed to reproduce a run.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Replay {
+    pub seed: u64,
+    /// Hashes of the configs the run was recorded with.
+    pub hashes: ConfigHashes,
+    /// Player input for every simulation tick.
+    pub ticks: Vec<ReplayTick>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReplayTick {
+    pub input: PlayerInput,
+    pub actions: Vec<PlayerAction>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigHashes {
+    pub config: u64,
+    pub level: u64,
+    pub enemies: u64,
+    pub bosses: u64,
+    pub weapons: u64,
+    pub waves: u64,
+}
+
+/// Records the ticks of the current run and saves them to a file.
+pub struct ReplayRecorder {
+    path: PathBuf,
+    replay: Replay,
+    saved: bool,
+}
+
+/// Feeds the ticks of a recorded run back to the game.
+pub struct ReplayPlayer {
+    pub replay: Replay,
+    next_tick: usize,
+}
+
+impl Replay {
+    pub fn new(model: &Model) -> Self {
+        Self {
+            seed: model.seed,
+            hashes: ConfigHashes::new(model),
+            ticks: Vec::new(),
+        }
+    }
+
+    pub async fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        crate::util::load_file(path).await
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let data = ron::ser::to_string(self)?;
+        std::fs::write(path, data).context(format!("when saving {:?}", path))?;
+        Ok(())
+    }
+}
+
+impl ConfigHashes {
+    pub fn new(model: &Model) -> Self {
+        // Sort enemies by name, since the iteration order of a `HashMap` is random
+        let enemies: BTreeMap<_, _> = model.enemies_list.iter().collect();
+        let bosses: BTreeMap<_, _> = model.bosses.iter().collect();
+        let weapons: BTreeMap<_, _> = model.weapons.iter().collect();
+        Self {
+            config: hash_debug(&model.config),
+            level: hash_debug(&model.level),
+            enemies: hash_debug(&enemies),
+            bosses: hash_debug(&bosses),
+            weapons: hash_debug(&weapons),
+            waves: hash_debug(&model.waves),
+        }
+    }
+}
+
+/// Hash the debug representation of the value.
+/// Most configs contain floats, so they do not implement [Hash] themselves.
+///
+/// Uses FNV-1a, since the output of the std hashers may change between Rust versions,
+/// which would invalidate the saved replays.
+fn hash_debug(value: &impl std::fmt::Debug) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    format!("{:?}", value)
+        .bytes()
+        .fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        })
+}
+
+impl ReplayRecorder {
+    pub fn new(path: PathBuf, model: &Model) -> Self {
+        Self {
+            path,
+            replay: Replay::new(model),
+            saved: false,
+        }
+    }
+
+    pub fn record(&mut self, tick: ReplayTick) {
+        self.replay.ticks.push(tick);
+    }
+
+    /// Save the replay, unless it has already been saved or nothing has been recorded.
+    pub fn save(&mut self) {
+        if self.saved || self.replay.ticks.is_empty() {
+            return;
+        }
+        self.saved = true;
+        match self.replay.save(&self.path) {
+            Ok(()) => log::info!("Saved the replay to {:?}", self.path),
+            Err(err) => log::error!("Failed to save the replay: {:?}", err),
+        }
+    }
+}
+
+/// Path to record the run to, numbered after the first one,
+/// so that restarting does not overwrite the previous runs.
+/// For example, `replay.ron`, `replay.1.ron`, `replay.2.ron` and so on.
+pub fn run_path(path: &Path, run: usize) -> PathBuf {
+    if run == 0 {
+        return path.to_path_buf();
+    }
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{}.{}.{}", stem, run, extension.to_string_lossy()),
+        None => format!("{}.{}", stem, run),
+    };
+    path.with_file_name(name)
+}
+
+impl Drop for ReplayRecorder {
+    /// Keep the recording when quitting in the middle of a run.
+    fn drop(&mut self) {
+        self.save();
+    }
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            next_tick: 0,
+        }
+    }
+
+    /// Start the playback from the beginning.
+    pub fn restart(&mut self) {
+        self.next_tick = 0;
+    }
+
+    /// Returns the input for the next tick, or `None` if the replay is over.
+    pub fn next_tick(&mut self) -> Option<ReplayTick> {
+        let tick = self.replay.ticks.get(self.next_tick)?.clone();
+        self.next_tick += 1;
+        Some(tick)
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{
+    assets::{
+        bosses::BossConfig,
+        config::{Config, LevelConfig},
+        theme::Theme,
+        waves::WavesConfig,
+    },
+    model::*,
+    prelude::*,
+    replay::{ConfigHashes, Replay, ReplayTick},
+};
+
+const SEED: u64 = 42;
+const TICKS: usize = 300;
+
+/// A model with the default configs from `assets`.
+fn new_model(seed: u64) -> Model {
+    futures::executor::block_on(async {
+        let config = Config::load("assets/config.ron").await.unwrap();
+        let level: LevelConfig = crate::util::load_file("assets/level.ron").await.unwrap();
+        let weapons = Config::load_weapons("assets/weapons/").await.unwrap();
+        let enemies = Config::load_enemies("assets/enemies/", &weapons)
+            .await
+            .unwrap();
+        let waves = WavesConfig::load("assets/waves.ron").await.unwrap();
+        let bosses = BossConfig::load_all("assets/bosses/", &enemies, &waves)
+            .await
+            .unwrap();
+        let theme = Theme::load("assets/theme.toml").await.unwrap();
+        Model::new(theme, config, level, enemies, bosses, weapons, waves, seed)
+    })
+}
+
+/// Scripted input: circle around while shooting, switching the state now and then.
+fn scripted_tick(model: &Model, tick: usize) -> ReplayTick {
+    let angle = Angle::from_degrees(r32(tick as f32 * 3.0));
+    let player = model
+        .actors
+        .body
+        .collider
+        .position
+        .get(model.player.actor)
+        .copied()
+        .unwrap_or(model.camera.center);
+    let target_pos = player.shifted(angle.unit_vec() * r32(20.0));
+
+    let mut actions = vec![PlayerAction::Shoot { target_pos }];
+    if tick % 120 == 60 {
+        actions.push(PlayerAction::SwitchState);
+    }
+    ReplayTick {
+        input: PlayerInput {
+            aim_at: target_pos,
+            direction: angle.unit_vec(),
+            drip_gas: tick % 50 < 10,
+        },
+        actions,
+    }
+}
+
+/// Simulate a tick the same way the game does.
+fn step(model: &mut Model, tick: ReplayTick) {
+    model.player.input = tick.input;
+    for action in tick.actions {
+        model.player_action(action);
+    }
+    let delta_time = R32::ONE / model.config.tick_rate;
+    model.update(delta_time);
+}
+
+/// The saved snapshot of the model, parsed back into a generic value,
+/// so that the iteration order of the `HashMap`s does not affect the comparison.
+fn saved_state(model: &Model, name: &str) -> ron::Value {
+    let path = std::env::temp_dir().join(format!("{}-{}.ron", name, std::process::id()));
+    model
+        .snapshot()
+        .expect("the player died during the test")
+        .save(&path)
+        .unwrap();
+    let data = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    ron::from_str(&data).unwrap()
+}
+
+#[test]
+fn snapshot_round_trip() {
+    let mut model = new_model(SEED);
+    for tick in 0..TICKS {
+        let input = scripted_tick(&model, tick);
+        step(&mut model, input);
+    }
+
+    let path = std::env::temp_dir().join(format!("round-trip-{}.ron", std::process::id()));
+    model.snapshot().unwrap().save(&path).unwrap();
+    let mut loaded = Model::from_snapshot(ModelSnapshot::load(&path).unwrap());
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        saved_state(&model, "original"),
+        saved_state(&loaded, "loaded")
+    );
+
+    // The loaded model continues the same way
+    for tick in TICKS..TICKS * 2 {
+        let input = scripted_tick(&model, tick);
+        step(&mut model, input.clone());
+        step(&mut loaded, input);
+    }
+    assert_eq!(
+        saved_state(&model, "original"),
+        saved_state(&loaded, "loaded")
+    );
+}
+
+#[test]
+fn replay_reproduces_the_run() {
+    let mut model = new_model(SEED);
+    let mut replay = Replay::new(&model);
+    for tick in 0..TICKS {
+        let input = scripted_tick(&model, tick);
+        replay.ticks.push(input.clone());
+        step(&mut model, input);
+    }
+
+    let path = std::env::temp_dir().join(format!("replay-{}.ron", std::process::id()));
+    replay.save(&path).unwrap();
+    let replay: Replay = futures::executor::block_on(Replay::load(&path)).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let mut replayed = new_model(replay.seed);
+    assert_eq!(replay.hashes, ConfigHashes::new(&replayed));
+    for tick in replay.ticks {
+        step(&mut replayed, tick);
+    }
+    assert_eq!(
+        saved_state(&model, "recorded"),
+        saved_state(&replayed, "replayed")
+    );
+}
@@ -1,3 +1,4 @@
 mod file;
+mod timestep;
 
-pub use self::file::*;
+pub use self::{file::*, timestep::*};
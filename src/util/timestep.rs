@@ -0,0 +1,42 @@
+use geng::prelude::*;
+
+/// Splits the frame time into fixed simulation ticks.
+#[derive(Debug, Clone)]
+pub struct FixedTimestep {
+    /// Duration of a single tick.
+    pub tick: R32,
+    /// Time accumulated, but not yet simulated.
+    accumulator: R32,
+}
+
+impl FixedTimestep {
+    /// Limit the number of ticks per frame, so that a long frame
+    /// does not make the simulation fall further and further behind.
+    const MAX_TICKS_PER_FRAME: usize = 5;
+
+    pub fn new(tick_rate: R32) -> Self {
+        Self {
+            tick: R32::ONE / tick_rate,
+            accumulator: R32::ZERO,
+        }
+    }
+
+    /// Accumulate the frame time and return the number of ticks to simulate.
+    pub fn advance(&mut self, delta_time: R32) -> usize {
+        self.accumulator += delta_time;
+        let mut ticks = 0;
+        while self.accumulator >= self.tick && ticks < Self::MAX_TICKS_PER_FRAME {
+            self.accumulator -= self.tick;
+            ticks += 1;
+        }
+        // Drop the time we could not catch up with
+        self.accumulator = self.accumulator.min(self.tick);
+        ticks
+    }
+
+    /// How far into the next tick the simulation is, in range `0..=1`.
+    /// Used to interpolate the rendering between ticks.
+    pub fn alpha(&self) -> R32 {
+        (self.accumulator / self.tick).clamp(R32::ZERO, R32::ONE)
+    }
+}